@@ -0,0 +1,113 @@
+//! Procedural macros for [`chksum-build`](https://docs.rs/chksum-build) crate.
+//!
+//! # Usage
+//!
+//! Add both crates to `dependencies` section of `Cargo.toml` and set up `chksum-build` build script.
+//!
+//! ```toml
+//! [dependencies]
+//! # ...
+//! chksum-build = "0.0.3"
+//! chksum-build-macros = "0.0.3"
+//! ```
+//!
+//! ## `build_info` macro
+//!
+//! [`build_info!`] macro parses environment variables emitted by build script at compile time and creates
+//! `BuildInfo` as a constant expression. Missing or malformed variables are reported as compilation errors.
+//!
+//! ```rust,ignore
+//! use chksum_build::BuildInfo;
+//! use chksum_build_macros::build_info;
+//!
+//! const BUILD_INFO: BuildInfo = build_info!();
+//! const MYAPP_BUILD_INFO: BuildInfo = build_info!(prefix = "MYAPP");
+//! ```
+
+#![forbid(unsafe_code)]
+
+use proc_macro::{Delimiter, Group, Ident, Literal, Punct, Spacing, Span, TokenStream, TokenTree};
+
+/// Default prefix of environment variables emitted by build script.
+const DEFAULT_PREFIX: &str = "CHKSUM_BUILD_INFO";
+
+/// Creates a `BuildInfo` from environment variables validated at compile time.
+///
+/// Unlike `chksum_build::build_info` macro it expands to a constant expression, so it can be used in const or static
+/// context without error handling.
+///
+/// Environment variables with custom prefix configured by `BuildScript::prefix` can be read with `prefix = "..."`
+/// argument.
+///
+/// Expression refers to items by `::chksum_build` path, so `chksum-build` cannot be renamed in `dependencies` section
+/// of `Cargo.toml`.
+///
+/// # Examples
+///
+/// ```rust,ignore
+/// use chksum_build::BuildInfo;
+/// use chksum_build_macros::build_info;
+///
+/// static BUILD_INFO: BuildInfo = build_info!();
+/// ```
+#[proc_macro]
+pub fn build_info(input: TokenStream) -> TokenStream {
+    let expr = parse_prefix(input).and_then(|prefix| {
+        chksum_build::__private::build_info_expr(&prefix).map_err(|error| {
+            format!("cannot create build info: {error}, check if chksum-build build script is set up with this prefix")
+        })
+    });
+
+    match expr.and_then(|expr| {
+        expr.parse()
+            .map_err(|error| format!("cannot parse build info: {error}"))
+    }) {
+        Ok(expr) => expr,
+        Err(error) => compile_error(&error),
+    }
+}
+
+/// Parses optional `prefix = "..."` argument.
+fn parse_prefix(input: TokenStream) -> Result<String, String> {
+    let tokens = input.into_iter().collect::<Vec<_>>();
+    let tokens = match tokens.as_slice() {
+        [arguments @ .., TokenTree::Punct(comma)] if !arguments.is_empty() && comma.as_char() == ',' => arguments,
+        tokens => tokens,
+    };
+
+    match tokens {
+        [] => Ok(String::from(DEFAULT_PREFIX)),
+        [TokenTree::Ident(name), TokenTree::Punct(equals), TokenTree::Literal(prefix)]
+            if name.to_string() == "prefix" && equals.as_char() == '=' =>
+        {
+            let prefix = prefix.to_string();
+            prefix
+                .strip_prefix('"')
+                .and_then(|prefix| prefix.strip_suffix('"'))
+                .filter(|prefix| !prefix.is_empty() && !prefix.contains('\\'))
+                .map(ToString::to_string)
+                .ok_or_else(|| format!("expected prefix to be a non-empty string literal, found {prefix}"))
+        },
+        _ => Err(String::from("expected no arguments or `prefix = \"...\"`")),
+    }
+}
+
+/// Creates `compile_error!` invocation with given message.
+fn compile_error(message: &str) -> TokenStream {
+    let span = Span::call_site();
+
+    let mut bang = Punct::new('!', Spacing::Alone);
+    bang.set_span(span);
+    let mut message = Literal::string(message);
+    message.set_span(span);
+    let mut arguments = Group::new(Delimiter::Parenthesis, TokenTree::Literal(message).into());
+    arguments.set_span(span);
+
+    [
+        TokenTree::Ident(Ident::new("compile_error", span)),
+        TokenTree::Punct(bang),
+        TokenTree::Group(arguments),
+    ]
+    .into_iter()
+    .collect()
+}
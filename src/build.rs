@@ -0,0 +1,122 @@
+//! Build related types.
+
+use std::fmt::{self, Display, Formatter};
+use std::result;
+use std::str::FromStr;
+
+use nom::branch::alt;
+use nom::bytes::complete::tag;
+use nom::combinator::all_consuming;
+use nom::error::{context, VerboseError};
+use nom::{Finish, IResult};
+
+use crate::error::Error;
+
+/// A source of build datetime.
+///
+/// Sources are checked in order: [`DatetimeSource::SourceDateEpoch`], [`DatetimeSource::Fixed`],
+/// [`DatetimeSource::GitCommit`] and [`DatetimeSource::Now`].
+///
+/// Resources:
+/// * [Reproducible Builds: `SOURCE_DATE_EPOCH`](https://reproducible-builds.org/specs/source-date-epoch/).
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum DatetimeSource {
+    /// Value of `SOURCE_DATE_EPOCH` environment variable.
+    SourceDateEpoch,
+    /// Datetime configured in build script.
+    Fixed,
+    /// Committer datetime of `HEAD` commit.
+    GitCommit,
+    /// Current datetime.
+    Now,
+}
+
+impl DatetimeSource {
+    const FIXED_STR: &'static str = "fixed";
+    const GIT_COMMIT_STR: &'static str = "git-commit";
+    const NOW_STR: &'static str = "now";
+    const SOURCE_DATE_EPOCH_STR: &'static str = "source-date-epoch";
+
+    /// Parse datetime source.
+    fn nom_parse(input: &str) -> IResult<&str, Self, VerboseError<&str>> {
+        let source_date_epoch = tag(Self::SOURCE_DATE_EPOCH_STR);
+        let fixed = tag(Self::FIXED_STR);
+        let git_commit = tag(Self::GIT_COMMIT_STR);
+        let now = tag(Self::NOW_STR);
+
+        let parser = alt((source_date_epoch, fixed, git_commit, now));
+
+        let (input, source) = context("datetime-source", parser)(input)?;
+
+        let source = match source {
+            Self::SOURCE_DATE_EPOCH_STR => Self::SourceDateEpoch,
+            Self::FIXED_STR => Self::Fixed,
+            Self::GIT_COMMIT_STR => Self::GitCommit,
+            Self::NOW_STR => Self::Now,
+            _ => unreachable!(),
+        };
+
+        Ok((input, source))
+    }
+}
+
+impl Display for DatetimeSource {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::SourceDateEpoch => write!(f, "{}", Self::SOURCE_DATE_EPOCH_STR),
+            Self::Fixed => write!(f, "{}", Self::FIXED_STR),
+            Self::GitCommit => write!(f, "{}", Self::GIT_COMMIT_STR),
+            Self::Now => write!(f, "{}", Self::NOW_STR),
+        }
+    }
+}
+
+impl FromStr for DatetimeSource {
+    type Err = Error;
+
+    fn from_str(s: &str) -> result::Result<Self, Self::Err> {
+        let parser = all_consuming(Self::nom_parse);
+
+        let (_, source) = context("datetime-source", parser)(s).finish().map_err(|error| {
+            let errors = error
+                .errors
+                .into_iter()
+                .map(|(input, kind)| (input.to_string(), kind))
+                .collect();
+            let error = VerboseError { errors };
+            Error::Nom(error)
+        })?;
+
+        Ok(source)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use anyhow::Result;
+
+    use super::*;
+
+    #[test]
+    fn test_datetime_source_display() {
+        assert_eq!(format!("{}", DatetimeSource::SourceDateEpoch), "source-date-epoch");
+        assert_eq!(format!("{}", DatetimeSource::Fixed), "fixed");
+        assert_eq!(format!("{}", DatetimeSource::GitCommit), "git-commit");
+        assert_eq!(format!("{}", DatetimeSource::Now), "now");
+    }
+
+    #[test]
+    fn test_datetime_source_from_str() -> Result<()> {
+        assert_eq!(
+            DatetimeSource::from_str("source-date-epoch")?,
+            DatetimeSource::SourceDateEpoch
+        );
+        assert!(DatetimeSource::from_str("SOURCE_DATE_EPOCH").is_err());
+        assert_eq!(DatetimeSource::from_str("fixed")?, DatetimeSource::Fixed);
+        assert_eq!(DatetimeSource::from_str("git-commit")?, DatetimeSource::GitCommit);
+        assert!(DatetimeSource::from_str("git").is_err());
+        assert_eq!(DatetimeSource::from_str("now")?, DatetimeSource::Now);
+        assert!(DatetimeSource::from_str("Now").is_err());
+        Ok(())
+    }
+}
@@ -1,42 +1,148 @@
 //! Cargo related types.
 
+use std::borrow::Cow;
 use std::fmt::{self, Display, Formatter};
 use std::result;
 use std::str::FromStr;
 
 use nom::branch::alt;
-use nom::bytes::complete::tag;
-use nom::combinator::all_consuming;
+use nom::bytes::complete::{tag, take_while1};
+#[cfg(feature = "script")]
+use nom::character::complete::digit1;
+use nom::combinator::{all_consuming, map_opt, opt};
+#[cfg(feature = "script")]
+use nom::combinator::{map_res, rest};
 use nom::error::{context, VerboseError};
+#[cfg(feature = "script")]
+use nom::sequence::terminated;
+use nom::sequence::{preceded, tuple};
 use nom::{Finish, IResult};
 
 use crate::error::Error;
 
 /// A Cargo profile.
 ///
+/// Custom profiles are displayed along with profile they inherit from, e.g. `dist:release`.
+///
 /// Resources:
 /// * [The Cargo Book: Profiles](https://doc.rust-lang.org/cargo/reference/profiles.html#profiles),
+/// * [The Cargo Book: Custom profiles](https://doc.rust-lang.org/cargo/reference/profiles.html#custom-profiles),
 /// * [The Cargo Book: Environment variables Cargo sets for build scripts](https://doc.rust-lang.org/cargo/reference/environment-variables.html#environment-variables-cargo-sets-for-build-scripts).
-#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[derive(Clone, Debug, Eq, PartialEq)]
 pub enum Profile {
     /// A release build.
     Release,
     /// A non release build.
     Debug,
+    /// A build with custom profile, e.g. `profile.dist`.
+    Custom {
+        /// Name of profile.
+        name: Cow<'static, str>,
+        /// Built-in profile which custom profile inherits from.
+        inherits: BaseProfile,
+    },
 }
 
 impl Profile {
+    const DEBUG_STR: &'static str = BaseProfile::DEBUG_STR;
+    const RELEASE_STR: &'static str = BaseProfile::RELEASE_STR;
+
+    /// Returns name of profile, e.g. `release` or `dist`.
+    #[inline]
+    #[must_use]
+    pub fn name(&self) -> &str {
+        match self {
+            Self::Release => Self::RELEASE_STR,
+            Self::Debug => Self::DEBUG_STR,
+            Self::Custom { name, .. } => name,
+        }
+    }
+
+    /// Returns built-in profile which profile is or inherits from.
+    #[inline]
+    #[must_use]
+    pub const fn inherits(&self) -> BaseProfile {
+        match self {
+            Self::Release => BaseProfile::Release,
+            Self::Debug => BaseProfile::Debug,
+            Self::Custom { inherits, .. } => *inherits,
+        }
+    }
+
+    /// Parse profile.
+    fn nom_parse(input: &str) -> IResult<&str, Self, VerboseError<&str>> {
+        let name = take_while1(|char: char| char.is_ascii_alphanumeric() || char == '-' || char == '_');
+        let inherits = opt(preceded(tag(":"), BaseProfile::nom_parse));
+
+        let parser = map_opt(tuple((name, inherits)), |(name, inherits)| {
+            match (name, inherits) {
+                (Self::RELEASE_STR, None) => Some(Self::Release),
+                (Self::DEBUG_STR, None) => Some(Self::Debug),
+                (Self::RELEASE_STR | Self::DEBUG_STR, Some(_)) | (_, None) => None,
+                (name, Some(inherits)) => {
+                    Some(Self::Custom {
+                        name: Cow::Owned(name.to_string()),
+                        inherits,
+                    })
+                },
+            }
+        });
+
+        context("profile", parser)(input)
+    }
+}
+
+impl Display for Profile {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Release => write!(f, "{}", Self::RELEASE_STR),
+            Self::Debug => write!(f, "{}", Self::DEBUG_STR),
+            Self::Custom { name, inherits } => write!(f, "{name}:{inherits}"),
+        }
+    }
+}
+
+impl FromStr for Profile {
+    type Err = Error;
+
+    fn from_str(s: &str) -> result::Result<Self, Self::Err> {
+        let parser = all_consuming(Self::nom_parse);
+
+        let (_, profile) = context("profile", parser)(s).finish().map_err(|error| {
+            let errors = error
+                .errors
+                .into_iter()
+                .map(|(input, kind)| (input.to_string(), kind))
+                .collect();
+            let error = VerboseError { errors };
+            Error::Nom(error)
+        })?;
+
+        Ok(profile)
+    }
+}
+
+/// A built-in Cargo profile set in `PROFILE` variable.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum BaseProfile {
+    /// A release build.
+    Release,
+    /// A non release build.
+    Debug,
+}
+
+impl BaseProfile {
     const DEBUG_STR: &'static str = "debug";
     const RELEASE_STR: &'static str = "release";
 
-    /// Parse profile.
+    /// Parse base profile.
     fn nom_parse(input: &str) -> IResult<&str, Self, VerboseError<&str>> {
         let release = tag(Self::RELEASE_STR);
         let debug = tag(Self::DEBUG_STR);
 
         let parser = alt((release, debug));
 
-        let (input, profile) = context("profile", parser)(input)?;
+        let (input, profile) = context("base-profile", parser)(input)?;
 
         let profile = match profile {
             Self::RELEASE_STR => Self::Release,
@@ -48,7 +154,7 @@ impl Profile {
     }
 }
 
-impl Display for Profile {
+impl Display for BaseProfile {
     fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
         match self {
             Self::Release => write!(f, "{}", Self::RELEASE_STR),
@@ -57,13 +163,13 @@ impl Display for Profile {
     }
 }
 
-impl FromStr for Profile {
+impl FromStr for BaseProfile {
     type Err = Error;
 
     fn from_str(s: &str) -> result::Result<Self, Self::Err> {
         let parser = all_consuming(Self::nom_parse);
 
-        let (_, profile) = context("profile", parser)(s).finish().map_err(|error| {
+        let (_, profile) = context("base-profile", parser)(s).finish().map_err(|error| {
             let errors = error
                 .errors
                 .into_iter()
@@ -77,6 +183,62 @@ impl FromStr for Profile {
     }
 }
 
+/// A Cargo version printed by `cargo --version`.
+#[cfg(feature = "script")]
+#[derive(Clone, Copy, Debug, Eq, Ord, PartialEq, PartialOrd)]
+pub(crate) struct CargoVersion {
+    pub(crate) major: usize,
+    pub(crate) minor: usize,
+    pub(crate) patch: usize,
+}
+
+#[cfg(feature = "script")]
+impl CargoVersion {
+    /// Parse version followed by optional pre-release and commit information.
+    fn nom_parse(input: &str) -> IResult<&str, Self, VerboseError<&str>> {
+        let major = context("major", map_res(terminated(digit1, tag(".")), str::parse));
+        let minor = context("minor", map_res(terminated(digit1, tag(".")), str::parse));
+        let patch = context("patch", map_res(digit1, str::parse));
+
+        let parser = preceded(tag("cargo "), terminated(tuple((major, minor, patch)), rest));
+
+        let (input, (major, minor, patch)) = context("cargo-version", parser)(input)?;
+
+        let version = Self { major, minor, patch };
+
+        Ok((input, version))
+    }
+
+    /// Checks whether `rustc-check-cfg` instruction is supported.
+    ///
+    /// Resources:
+    /// * [The Cargo Book: `cargo::rustc-check-cfg`](https://doc.rust-lang.org/cargo/reference/build-scripts.html#rustc-check-cfg).
+    pub(crate) fn supports_check_cfg(self) -> bool {
+        (self.major, self.minor) >= (1, 80)
+    }
+}
+
+#[cfg(feature = "script")]
+impl FromStr for CargoVersion {
+    type Err = Error;
+
+    fn from_str(s: &str) -> result::Result<Self, Self::Err> {
+        let parser = all_consuming(Self::nom_parse);
+
+        let (_, version) = context("cargo-version", parser)(s).finish().map_err(|error| {
+            let errors = error
+                .errors
+                .into_iter()
+                .map(|(input, kind)| (input.to_string(), kind))
+                .collect();
+            let error = VerboseError { errors };
+            Error::Nom(error)
+        })?;
+
+        Ok(version)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use anyhow::Result;
@@ -98,6 +260,66 @@ mod tests {
         assert!(Profile::from_str("dbg").is_err());
         assert!(Profile::from_str("Debug").is_err());
         assert!(Profile::from_str("DEBUG").is_err());
+        assert_eq!(
+            Profile::from_str("bench-lto:release")?,
+            Profile::Custom {
+                name: Cow::Borrowed("bench-lto"),
+                inherits: BaseProfile::Release,
+            }
+        );
+        assert_eq!(Profile::from_str("dist:debug")?.inherits(), BaseProfile::Debug);
+        assert_eq!(Profile::from_str("dist:debug")?.name(), "dist");
+        assert!(Profile::from_str("dist").is_err());
+        assert!(Profile::from_str("dist:").is_err());
+        assert!(Profile::from_str("dist:dev").is_err());
+        assert!(Profile::from_str("release:release").is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_custom_profile_display() {
+        let profile = Profile::Custom {
+            name: Cow::Borrowed("dist"),
+            inherits: BaseProfile::Release,
+        };
+        assert_eq!(format!("{profile}"), "dist:release");
+        assert_eq!(profile.name(), "dist");
+        assert_eq!(Profile::Debug.name(), "debug");
+        assert_eq!(Profile::Release.inherits(), BaseProfile::Release);
+    }
+
+    #[cfg(feature = "script")]
+    #[test]
+    fn test_cargo_version_from_str() -> Result<()> {
+        assert_eq!(
+            CargoVersion::from_str("cargo 1.80.0 (376290515 2024-07-16)")?,
+            CargoVersion {
+                major: 1,
+                minor: 80,
+                patch: 0
+            }
+        );
+        assert_eq!(
+            CargoVersion::from_str("cargo 1.81.0-nightly (154fdac39 2024-07-07)")?,
+            CargoVersion {
+                major: 1,
+                minor: 81,
+                patch: 0
+            }
+        );
+        assert_eq!(
+            CargoVersion::from_str("cargo 1.58.0")?,
+            CargoVersion {
+                major: 1,
+                minor: 58,
+                patch: 0
+            }
+        );
+        assert!(CargoVersion::from_str("cargo 1.80").is_err());
+        assert!(CargoVersion::from_str("rustc 1.80.0").is_err());
+        assert!(!CargoVersion::from_str("cargo 1.79.0")?.supports_check_cfg());
+        assert!(CargoVersion::from_str("cargo 1.80.0")?.supports_check_cfg());
+        assert!(CargoVersion::from_str("cargo 2.0.0")?.supports_check_cfg());
         Ok(())
     }
 }
@@ -0,0 +1,643 @@
+//! Generation of Rust source file with constant build information.
+//!
+//! Values are taken from environment variables emitted by build script sections, so generated
+//! `BUILD_INFO` constant contains the same data as `build_info` macro would create, validated once at build time.
+//!
+//! Build script and procedural macro cannot tell how crate is named by dependent crate, so expressions refer to
+//! items by `::chksum_build` path.
+
+use std::collections::BTreeMap;
+use std::fmt::Display;
+use std::str::FromStr;
+use std::{env, result};
+
+use chrono::{DateTime, Datelike, FixedOffset, NaiveDate, Timelike};
+
+use crate::build::DatetimeSource;
+use crate::cargo::{BaseProfile, Profile};
+use crate::error::{Error, Result};
+use crate::package::PackageVersion;
+use crate::rust::{Channel, ChannelVersion, Host};
+use crate::target::{Endian, Panic};
+
+/// Formats value as a constant Rust expression.
+pub(crate) trait ConstExpr {
+    /// Returns constant expression which creates value.
+    fn const_expr(&self) -> String;
+}
+
+impl<T> ConstExpr for &T
+where
+    T: ConstExpr + ?Sized,
+{
+    fn const_expr(&self) -> String {
+        (**self).const_expr()
+    }
+}
+
+impl ConstExpr for str {
+    fn const_expr(&self) -> String {
+        format!("::std::borrow::Cow::Borrowed({self:?})")
+    }
+}
+
+impl ConstExpr for [&str] {
+    fn const_expr(&self) -> String {
+        // list is borrowed from inner constant since temporaries passed to functions aren't promoted
+        let items = self.iter().map(|item| item.const_expr()).collect::<Vec<_>>().join(", ");
+        format!(
+            "::std::borrow::Cow::Borrowed({{ const LIST: &[::std::borrow::Cow<'static, str>] = &[{items}]; LIST }})"
+        )
+    }
+}
+
+impl<T> ConstExpr for Option<T>
+where
+    T: ConstExpr,
+{
+    fn const_expr(&self) -> String {
+        match self {
+            Some(value) => format!("::std::option::Option::Some({})", value.const_expr()),
+            None => String::from("::std::option::Option::None"),
+        }
+    }
+}
+
+impl ConstExpr for bool {
+    fn const_expr(&self) -> String {
+        self.to_string()
+    }
+}
+
+impl ConstExpr for u32 {
+    fn const_expr(&self) -> String {
+        self.to_string()
+    }
+}
+
+impl ConstExpr for u64 {
+    fn const_expr(&self) -> String {
+        self.to_string()
+    }
+}
+
+impl ConstExpr for usize {
+    fn const_expr(&self) -> String {
+        self.to_string()
+    }
+}
+
+impl ConstExpr for NaiveDate {
+    fn const_expr(&self) -> String {
+        let (year, month, day) = (self.year(), self.month(), self.day());
+        format!("::chksum_build::__private::date({year}, {month}, {day})")
+    }
+}
+
+impl ConstExpr for DateTime<FixedOffset> {
+    fn const_expr(&self) -> String {
+        let datetime = self.naive_utc();
+        let (year, month, day) = (datetime.year(), datetime.month(), datetime.day());
+        let (hour, minute, second, nanosecond) = (
+            datetime.hour(),
+            datetime.minute(),
+            datetime.second(),
+            datetime.nanosecond(),
+        );
+        let offset = self.offset().local_minus_utc();
+        format!(
+            "::chksum_build::__private::datetime({year}, {month}, {day}, {hour}, {minute}, {second}, {nanosecond}, \
+             {offset})"
+        )
+    }
+}
+
+impl ConstExpr for DatetimeSource {
+    fn const_expr(&self) -> String {
+        let variant = match self {
+            Self::SourceDateEpoch => "SourceDateEpoch",
+            Self::Fixed => "Fixed",
+            Self::GitCommit => "GitCommit",
+            Self::Now => "Now",
+        };
+        format!("::chksum_build::DatetimeSource::{variant}")
+    }
+}
+
+impl ConstExpr for BaseProfile {
+    fn const_expr(&self) -> String {
+        let variant = match self {
+            Self::Release => "Release",
+            Self::Debug => "Debug",
+        };
+        format!("::chksum_build::BaseProfile::{variant}")
+    }
+}
+
+impl ConstExpr for Profile {
+    fn const_expr(&self) -> String {
+        match self {
+            Self::Release => String::from("::chksum_build::Profile::Release"),
+            Self::Debug => String::from("::chksum_build::Profile::Debug"),
+            Self::Custom { name, inherits } => {
+                format!(
+                    "::chksum_build::Profile::Custom {{ name: {}, inherits: {} }}",
+                    name.const_expr(),
+                    inherits.const_expr()
+                )
+            },
+        }
+    }
+}
+
+impl ConstExpr for ChannelVersion {
+    fn const_expr(&self) -> String {
+        match self {
+            Self::MajorMinor(major, minor) => format!("::chksum_build::ChannelVersion::MajorMinor({major}, {minor})"),
+            Self::MajorMinorPatch(major, minor, patch) => {
+                format!("::chksum_build::ChannelVersion::MajorMinorPatch({major}, {minor}, {patch})")
+            },
+        }
+    }
+}
+
+impl ConstExpr for Channel {
+    fn const_expr(&self) -> String {
+        match self {
+            Self::Stable => String::from("::chksum_build::Channel::Stable"),
+            Self::Beta => String::from("::chksum_build::Channel::Beta"),
+            Self::Nightly => String::from("::chksum_build::Channel::Nightly"),
+            Self::Version(version) => format!("::chksum_build::Channel::Version({})", version.const_expr()),
+        }
+    }
+}
+
+impl ConstExpr for Host {
+    fn const_expr(&self) -> String {
+        format!(
+            "::chksum_build::Host::new({}, {}, {})",
+            self.architecture().const_expr(),
+            self.vendor().const_expr(),
+            self.system().const_expr()
+        )
+    }
+}
+
+impl ConstExpr for Endian {
+    fn const_expr(&self) -> String {
+        let variant = match self {
+            Self::Big => "Big",
+            Self::Little => "Little",
+        };
+        format!("::chksum_build::Endian::{variant}")
+    }
+}
+
+impl ConstExpr for Panic {
+    fn const_expr(&self) -> String {
+        let variant = match self {
+            Self::Abort => "Abort",
+            Self::ImmediateAbort => "ImmediateAbort",
+            Self::Unwind => "Unwind",
+            Self::Other(other) => return format!("::chksum_build::Panic::Other({})", other.const_expr()),
+        };
+        format!("::chksum_build::Panic::{variant}")
+    }
+}
+
+impl ConstExpr for PackageVersion {
+    fn const_expr(&self) -> String {
+        format!(
+            "::chksum_build::PackageVersion::new({}, {}, {}, {}, {})",
+            self.major(),
+            self.minor(),
+            self.patch(),
+            self.pre().const_expr(),
+            self.build().const_expr()
+        )
+    }
+}
+
+/// Values of environment variables emitted by build script.
+pub(crate) struct Values {
+    prefix: String,
+    values: BTreeMap<String, String>,
+}
+
+impl Values {
+    /// Collects values of `cargo:rustc-env` instructions with given prefix.
+    pub(crate) fn parse(stdout: &str, prefix: &str) -> Self {
+        let vars = stdout
+            .lines()
+            .filter_map(|line| line.strip_prefix("cargo:rustc-env="))
+            .filter_map(|line| line.split_once('='))
+            .map(|(name, value)| (name.to_string(), value.to_string()));
+        Self::from_vars(vars, prefix)
+    }
+
+    /// Collects values of environment variables with given prefix.
+    pub(crate) fn from_vars<I>(vars: I, prefix: &str) -> Self
+    where
+        I: IntoIterator<Item = (String, String)>,
+    {
+        let values = vars
+            .into_iter()
+            .filter_map(|(name, value)| {
+                let name = name.strip_prefix(prefix)?.strip_prefix('_')?;
+                Some((name.to_string(), value))
+            })
+            .collect();
+        let prefix = prefix.to_string();
+        Self { prefix, values }
+    }
+
+    fn required(&self, name: &str) -> Result<&str> {
+        self.optional(name).ok_or_else(|| {
+            let prefix = &self.prefix;
+            Error::Codegen(format!("missing {prefix}_{name} variable"))
+        })
+    }
+
+    fn optional(&self, name: &str) -> Option<&str> {
+        self.values.get(name).map(String::as_str)
+    }
+
+    fn required_with<T, E, P>(&self, name: &str, parse: P) -> Result<T>
+    where
+        E: Display,
+        P: FnOnce(&str) -> result::Result<T, E>,
+    {
+        let value = self.required(name)?;
+        self.parse_value(name, value, parse)
+    }
+
+    fn optional_with<T, E, P>(&self, name: &str, parse: P) -> Result<Option<T>>
+    where
+        E: Display,
+        P: FnOnce(&str) -> result::Result<T, E>,
+    {
+        self.optional(name)
+            .map(|value| self.parse_value(name, value, parse))
+            .transpose()
+    }
+
+    fn list(&self, name: &str) -> Vec<&str> {
+        self.optional(name)
+            .map(|list| list.split(',').filter(|item| !item.is_empty()).collect())
+            .unwrap_or_default()
+    }
+
+    fn parse_value<T, E, P>(&self, name: &str, value: &str, parse: P) -> Result<T>
+    where
+        E: Display,
+        P: FnOnce(&str) -> result::Result<T, E>,
+    {
+        parse(value).map_err(|error| {
+            let prefix = &self.prefix;
+            Error::Codegen(format!("invalid {prefix}_{name} value {value:?}: {error}"))
+        })
+    }
+}
+
+/// Renders source file with `BUILD_INFO` constant and `build_info` function.
+///
+/// Package informations are read from `CARGO_PKG_*` variables with `var` function.
+pub(crate) fn render<F>(values: &Values, var: F) -> Result<String>
+where
+    F: Fn(&str) -> Option<String>,
+{
+    let build_info = render_build_info(values, var)?;
+
+    Ok(format!(
+        "// Generated by chksum-build build script, do not edit.
+
+const BUILD_INFO: ::chksum_build::BuildInfo = {build_info};
+
+/// Returns build information shared by whole crate.
+#[allow(dead_code)]
+fn build_info() -> &'static ::chksum_build::BuildInfo {{
+    static BUILD_INFO_STATIC: ::chksum_build::BuildInfo = BUILD_INFO;
+
+    &BUILD_INFO_STATIC
+}}
+"
+    ))
+}
+
+/// Renders constant expression which creates [`BuildInfo`](crate::BuildInfo).
+///
+/// Package informations are read from `CARGO_PKG_*` variables with `var` function.
+pub(crate) fn render_build_info<F>(values: &Values, var: F) -> Result<String>
+where
+    F: Fn(&str) -> Option<String>,
+{
+    let build = render_section(values, "BUILD_DATETIME", render_build)?;
+    let cargo = render_section(values, "CARGO_PROFILE", render_cargo)?;
+    let rust = render_section(values, "RUST_CHANNEL", render_rust)?;
+    let target = render_section(values, "TARGET_HOST", render_target)?;
+    let package = render_package(var)?;
+    let git = render_section(values, "GIT_COMMIT_HASH", render_git)?;
+
+    Ok(format!(
+        "::chksum_build::BuildInfo::new(\n    {build},\n    {cargo},\n    {rust},\n    {target},\n    {package},\n    \
+         {git},\n)"
+    ))
+}
+
+/// Renders constant expression which creates [`BuildInfo`](crate::BuildInfo) from environment variables of current
+/// process with given prefix.
+///
+/// Used by `chksum-build-macros` crate, which reads variables set by build script during compilation.
+///
+/// # Errors
+///
+/// Returns an error when any of required variables is missing or malformed.
+pub fn build_info_expr(prefix: &str) -> Result<String> {
+    // Variables which are not valid Unicode cannot be set by build script, so they are skipped instead of panicking.
+    let vars = env::vars_os().filter_map(|(name, value)| Some((name.into_string().ok()?, value.into_string().ok()?)));
+    let values = Values::from_vars(vars, prefix);
+    render_build_info(&values, |name| env::var(name).ok())
+}
+
+/// Renders `Option` of section, section is `None` when build script didn't emit given variable.
+fn render_section<R>(values: &Values, name: &str, render: R) -> Result<String>
+where
+    R: FnOnce(&Values) -> Result<String>,
+{
+    match values.optional(name) {
+        Some(_) => render(values).map(|section| format!("::std::option::Option::Some({section})")),
+        None => Ok(String::from("::std::option::Option::None")),
+    }
+}
+
+fn render_build(values: &Values) -> Result<String> {
+    let datetime = values.required_with("BUILD_DATETIME", DateTime::parse_from_rfc3339)?;
+    let datetime_source = values.required_with("BUILD_DATETIME_SOURCE", DatetimeSource::from_str)?;
+
+    Ok(format!(
+        "::chksum_build::Build::new({}, {})",
+        datetime.const_expr(),
+        datetime_source.const_expr()
+    ))
+}
+
+fn render_cargo(values: &Values) -> Result<String> {
+    let profile = values.required_with("CARGO_PROFILE", Profile::from_str)?;
+    let opt_level = values.required("CARGO_OPT_LEVEL")?;
+    let debug = values.required_with("CARGO_DEBUG", bool::from_str)?;
+    let debug_assertions = values.required_with("CARGO_DEBUG_ASSERTIONS", bool::from_str)?;
+    let features = values.list("CARGO_FEATURES");
+
+    Ok(format!(
+        "::chksum_build::Cargo::new({}, {}, {}, {}, {})",
+        profile.const_expr(),
+        opt_level.const_expr(),
+        debug.const_expr(),
+        debug_assertions.const_expr(),
+        features.const_expr()
+    ))
+}
+
+fn render_rust(values: &Values) -> Result<String> {
+    let channel = values.required_with("RUST_CHANNEL", Channel::from_str)?;
+    let version = values.optional_with("RUST_VERSION", ChannelVersion::from_str)?;
+    let commit_hash = values.optional("RUST_COMMIT_HASH");
+    let commit_date = values.optional_with("RUST_COMMIT_DATE", |commit_date| {
+        NaiveDate::parse_from_str(commit_date, "%Y-%m-%d")
+    })?;
+    let llvm_version = values.optional("RUST_LLVM_VERSION");
+
+    Ok(format!(
+        "::chksum_build::Rust::new({}, {}, {}, {}, {})",
+        channel.const_expr(),
+        version.const_expr(),
+        commit_hash.const_expr(),
+        commit_date.const_expr(),
+        llvm_version.const_expr()
+    ))
+}
+
+fn render_target(values: &Values) -> Result<String> {
+    let host = values.required_with("TARGET_HOST", Host::from_str)?;
+    let target = values.required_with("TARGET_TRIPLE", Host::from_str)?;
+    let os = values.required("TARGET_CFG_OS")?;
+    let arch = values.required("TARGET_CFG_ARCH")?;
+    let env = values.optional("TARGET_CFG_ENV");
+    let vendor = values.required("TARGET_CFG_VENDOR")?;
+    let families = values.list("TARGET_CFG_FAMILY");
+    let endian = values.required_with("TARGET_CFG_ENDIAN", Endian::from_str)?;
+    let pointer_width = values.required_with("TARGET_CFG_POINTER_WIDTH", u32::from_str)?;
+    let atomics = values.list("TARGET_CFG_HAS_ATOMIC");
+    let features = values.list("TARGET_CFG_FEATURE");
+    let panic = values.optional_with("TARGET_CFG_PANIC", Panic::from_str)?;
+
+    let cfg = format!(
+        "::chksum_build::TargetCfg::new({}, {}, {}, {}, {}, {}, {}, {}, {}, {})",
+        os.const_expr(),
+        arch.const_expr(),
+        env.const_expr(),
+        vendor.const_expr(),
+        families.const_expr(),
+        endian.const_expr(),
+        pointer_width.const_expr(),
+        atomics.const_expr(),
+        features.const_expr(),
+        panic.const_expr()
+    );
+
+    Ok(format!(
+        "::chksum_build::Target::new({}, {}, {cfg})",
+        host.const_expr(),
+        target.const_expr()
+    ))
+}
+
+fn render_package<F>(var: F) -> Result<String>
+where
+    F: Fn(&str) -> Option<String>,
+{
+    let optional = |name: &str| var(name).filter(|value| !value.is_empty());
+    let required = |name: &str| optional(name).ok_or_else(|| Error::Codegen(format!("missing {name} variable")));
+
+    let name = required("CARGO_PKG_NAME")?;
+    let version = required("CARGO_PKG_VERSION")?;
+    let version = PackageVersion::from_str(&version)
+        .map_err(|error| Error::Codegen(format!("invalid CARGO_PKG_VERSION value {version:?}: {error}")))?;
+    let authors = optional("CARGO_PKG_AUTHORS").unwrap_or_default();
+    let authors = authors
+        .split(':')
+        .filter(|author| !author.is_empty())
+        .collect::<Vec<_>>();
+    let description = optional("CARGO_PKG_DESCRIPTION");
+    let homepage = optional("CARGO_PKG_HOMEPAGE");
+    let repository = optional("CARGO_PKG_REPOSITORY");
+    let license = optional("CARGO_PKG_LICENSE");
+    let rust_version = optional("CARGO_PKG_RUST_VERSION")
+        .map(|rust_version| {
+            ChannelVersion::from_str(&rust_version).map_err(|error| {
+                Error::Codegen(format!(
+                    "invalid CARGO_PKG_RUST_VERSION value {rust_version:?}: {error}"
+                ))
+            })
+        })
+        .transpose()?;
+
+    Ok(format!(
+        "::chksum_build::Package::new({}, {}, {}, {}, {}, {}, {}, {})",
+        name.const_expr(),
+        version.const_expr(),
+        authors.const_expr(),
+        description.as_deref().const_expr(),
+        homepage.as_deref().const_expr(),
+        repository.as_deref().const_expr(),
+        license.as_deref().const_expr(),
+        rust_version.const_expr()
+    ))
+}
+
+fn render_git(values: &Values) -> Result<String> {
+    let commit_hash = values.required("GIT_COMMIT_HASH")?;
+    let commit_short_hash = values.optional("GIT_COMMIT_SHORT_HASH").unwrap_or(commit_hash);
+    let commit_datetime = values.optional_with("GIT_COMMIT_DATETIME", DateTime::parse_from_rfc3339)?;
+    let dirty = values.optional("GIT_DIRTY") == Some("true");
+    let branch = values.optional("GIT_BRANCH");
+    let tag = values.optional("GIT_TAG");
+    let path_in_vcs = values.optional("GIT_PATH_IN_VCS");
+
+    Ok(format!(
+        "::chksum_build::Git::new({}, {}, {}, {}, {}, {}, {})",
+        commit_hash.const_expr(),
+        commit_short_hash.const_expr(),
+        commit_datetime.const_expr(),
+        dirty.const_expr(),
+        branch.const_expr(),
+        tag.const_expr(),
+        path_in_vcs.const_expr()
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_const_expr() -> Result<()> {
+        assert_eq!("a\"b".const_expr(), "::std::borrow::Cow::Borrowed(\"a\\\"b\")");
+        assert_eq!(
+            ["std", "alloc"].const_expr(),
+            "::std::borrow::Cow::Borrowed({ const LIST: &[::std::borrow::Cow<'static, str>] = \
+             &[::std::borrow::Cow::Borrowed(\"std\"), ::std::borrow::Cow::Borrowed(\"alloc\")]; LIST })"
+        );
+        assert_eq!(None::<&str>.const_expr(), "::std::option::Option::None");
+        assert_eq!(
+            Some(ChannelVersion::MajorMinor(1, 74)).const_expr(),
+            "::std::option::Option::Some(::chksum_build::ChannelVersion::MajorMinor(1, 74))"
+        );
+        assert_eq!(
+            DateTime::parse_from_rfc3339("2023-01-02T10:20:30.5+01:00")?.const_expr(),
+            "::chksum_build::__private::datetime(2023, 1, 2, 9, 20, 30, 500000000, 3600)"
+        );
+        assert_eq!(
+            Profile::from_str("dist:release")?.const_expr(),
+            "::chksum_build::Profile::Custom { name: ::std::borrow::Cow::Borrowed(\"dist\"), inherits: \
+             ::chksum_build::BaseProfile::Release }"
+        );
+        assert_eq!(
+            Host::from_str("x86_64-unknown-linux-gnu")?.const_expr(),
+            "::chksum_build::Host::new(::chksum_build::Architecture::x86_64, \
+             ::std::option::Option::Some(::chksum_build::Vendor::Unknown), \
+             ::chksum_build::System::Linux(::chksum_build::LinuxAbi::GNU))"
+        );
+        assert_eq!(
+            Host::from_str("future64-none-elf")?.const_expr(),
+            "::chksum_build::Host::new(::chksum_build::Architecture::Other(::std::borrow::Cow::Borrowed(\"future64\"\
+             )), ::std::option::Option::None, \
+             ::chksum_build::System::BareMetal(::std::option::Option::Some(::chksum_build::Abi::ELF)))"
+        );
+        assert_eq!(Panic::Unwind.const_expr(), "::chksum_build::Panic::Unwind");
+        assert_eq!(
+            Panic::from_str("abort-unwind")?.const_expr(),
+            "::chksum_build::Panic::Other(::std::borrow::Cow::Borrowed(\"abort-unwind\"))"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_values_parse() {
+        let values = Values::parse(
+            "cargo:rustc-cfg=chksum_profile=\"release\"\ncargo:rustc-env=MYAPP_CARGO_PROFILE=release\ncargo:\
+             rustc-env=MYAPP_CARGO_FEATURES=\ncargo:rustc-env=OTHER_CARGO_DEBUG=true\n",
+            "MYAPP",
+        );
+        assert_eq!(values.optional("CARGO_PROFILE"), Some("release"));
+        assert_eq!(values.list("CARGO_FEATURES"), Vec::<&str>::new());
+        assert_eq!(values.optional("CARGO_DEBUG"), None);
+        assert!(values.required("CARGO_DEBUG").is_err());
+        assert_eq!(
+            values.required_with("CARGO_PROFILE", Profile::from_str).ok(),
+            Some(Profile::Release)
+        );
+        assert!(values.optional_with("CARGO_PROFILE", u32::from_str).is_err());
+    }
+
+    #[test]
+    fn test_render() -> Result<()> {
+        let stdout = "\
+cargo:rustc-env=CHKSUM_BUILD_INFO_BUILD_DATETIME=2023-01-02T09:20:30Z
+cargo:rustc-env=CHKSUM_BUILD_INFO_BUILD_DATETIME_SOURCE=source-date-epoch
+cargo:rustc-env=CHKSUM_BUILD_INFO_CARGO_PROFILE=release
+cargo:rustc-env=CHKSUM_BUILD_INFO_CARGO_OPT_LEVEL=3
+cargo:rustc-env=CHKSUM_BUILD_INFO_CARGO_DEBUG=false
+cargo:rustc-env=CHKSUM_BUILD_INFO_CARGO_DEBUG_ASSERTIONS=false
+cargo:rustc-env=CHKSUM_BUILD_INFO_CARGO_FEATURES=default,std
+cargo:rustc-env=CHKSUM_BUILD_INFO_RUST_CHANNEL=stable
+cargo:rustc-env=CHKSUM_BUILD_INFO_RUST_VERSION=1.74.1
+cargo:rustc-env=CHKSUM_BUILD_INFO_TARGET_HOST=x86_64-unknown-linux-gnu
+cargo:rustc-env=CHKSUM_BUILD_INFO_TARGET_TRIPLE=x86_64-unknown-linux-gnu
+cargo:rustc-env=CHKSUM_BUILD_INFO_TARGET_CFG_OS=linux
+cargo:rustc-env=CHKSUM_BUILD_INFO_TARGET_CFG_ARCH=x86_64
+cargo:rustc-env=CHKSUM_BUILD_INFO_TARGET_CFG_VENDOR=unknown
+cargo:rustc-env=CHKSUM_BUILD_INFO_TARGET_CFG_ENDIAN=little
+cargo:rustc-env=CHKSUM_BUILD_INFO_TARGET_CFG_POINTER_WIDTH=64
+";
+        let var = |name: &str| {
+            match name {
+                "CARGO_PKG_NAME" => Some(String::from("crate")),
+                "CARGO_PKG_VERSION" => Some(String::from("0.1.0")),
+                "CARGO_PKG_DESCRIPTION" => Some(String::new()),
+                _ => None,
+            }
+        };
+
+        let source = render(&Values::parse(stdout, "CHKSUM_BUILD_INFO"), var)?;
+        assert!(source.contains("const BUILD_INFO: ::chksum_build::BuildInfo = ::chksum_build::BuildInfo::new(\n"));
+        assert!(source.contains(
+            "::chksum_build::Package::new(::std::borrow::Cow::Borrowed(\"crate\"), \
+             ::chksum_build::PackageVersion::new(0, 1, 0, ::std::option::Option::None, ::std::option::Option::None), \
+             ::std::borrow::Cow::Borrowed({ const LIST: &[::std::borrow::Cow<'static, str>] = &[]; LIST }), \
+             ::std::option::Option::None, ::std::option::Option::None, ::std::option::Option::None, \
+             ::std::option::Option::None, ::std::option::Option::None),\n"
+        ));
+        assert!(source.contains("    ::std::option::Option::None,\n);\n"));
+        assert!(source.contains("fn build_info() -> &'static ::chksum_build::BuildInfo {\n"));
+
+        let stdout = stdout.replace("CARGO_PROFILE=release", "CARGO_PROFILE=fast");
+        let error = render(&Values::parse(&stdout, "CHKSUM_BUILD_INFO"), var).unwrap_err();
+        assert!(error
+            .to_string()
+            .starts_with("codegen: invalid CHKSUM_BUILD_INFO_CARGO_PROFILE value \"fast\": "));
+        let stdout = stdout.replace("CHKSUM_BUILD_INFO_BUILD_DATETIME_SOURCE", "OTHER_BUILD_DATETIME_SOURCE");
+        let error = render(&Values::parse(&stdout, "CHKSUM_BUILD_INFO"), var).unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "codegen: missing CHKSUM_BUILD_INFO_BUILD_DATETIME_SOURCE variable"
+        );
+
+        let source = render(&Values::parse("", "CHKSUM_BUILD_INFO"), var)?;
+        assert!(source.contains(
+            "::chksum_build::BuildInfo::new(\n    ::std::option::Option::None,\n    ::std::option::Option::None,\n    \
+             ::std::option::Option::None,\n    ::std::option::Option::None,\n    ::chksum_build::Package::new("
+        ));
+        Ok(())
+    }
+}
@@ -1,4 +1,4 @@
-use std::{env, fmt, io, result};
+use std::{env, fmt, io, num, result};
 
 use {chrono, nom, thiserror};
 
@@ -9,17 +9,35 @@ pub enum Error {
     #[error(transparent)]
     ChronoParse(#[from] chrono::ParseError),
     #[cfg_attr(docsrs, doc(hidden))]
+    #[error("codegen: {0}")]
+    Codegen(String),
+    #[cfg_attr(docsrs, doc(hidden))]
     #[error(transparent)]
     EnvVar(#[from] env::VarError),
     #[cfg_attr(docsrs, doc(hidden))]
     #[error(transparent)]
     Fmt(#[from] fmt::Error),
     #[cfg_attr(docsrs, doc(hidden))]
+    #[error("git: {0}")]
+    Git(String),
+    #[cfg_attr(docsrs, doc(hidden))]
     #[error(transparent)]
     Io(#[from] io::Error),
     #[cfg_attr(docsrs, doc(hidden))]
+    #[error("missing {0} environment variable")]
+    MissingEnvVar(String),
+    #[cfg_attr(docsrs, doc(hidden))]
     #[error(transparent)]
     Nom(#[from] nom::error::VerboseError<String>),
+    #[cfg_attr(docsrs, doc(hidden))]
+    #[error(transparent)]
+    ParseInt(#[from] num::ParseIntError),
+    #[cfg_attr(docsrs, doc(hidden))]
+    #[error("rustc: {0}")]
+    Rustc(String),
+    #[cfg_attr(docsrs, doc(hidden))]
+    #[error("invalid SOURCE_DATE_EPOCH value {0:?}")]
+    SourceDateEpoch(String),
 }
 
 /// Type alias for [`Result`](std::result::Result) with an error type of [`Error`].
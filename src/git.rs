@@ -0,0 +1,1441 @@
+//! Git related items.
+//!
+//! Repository is read directly from `.git` directory, no `git` binary is required.
+//!
+//! Resources:
+//! * [Git Internals: Git References](https://git-scm.com/book/en/v2/Git-Internals-Git-References),
+//! * [Git Internals: Packfiles](https://git-scm.com/book/en/v2/Git-Internals-Packfiles),
+//! * [Git Documentation: Git index format](https://git-scm.com/docs/index-format),
+//! * [Git Documentation: Git pack format](https://git-scm.com/docs/pack-format).
+
+use std::collections::{BTreeMap, HashSet, VecDeque};
+use std::fs::{self, File};
+use std::io::{self, Read, Seek, SeekFrom};
+use std::path::{Path, PathBuf};
+use std::time::UNIX_EPOCH;
+
+use chrono::{DateTime, FixedOffset, TimeZone};
+use miniz_oxide::inflate::decompress_to_vec_zlib;
+use sha1_smol::Sha1;
+
+use crate::error::{Error, Result};
+use crate::json::Value;
+
+/// Informations about `HEAD` of a Git repository.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub(crate) struct Head {
+    pub(crate) commit_hash: String,
+    pub(crate) commit_datetime: Option<DateTime<FixedOffset>>,
+    pub(crate) dirty: bool,
+    pub(crate) branch: Option<String>,
+    pub(crate) tag: Option<String>,
+    pub(crate) path_in_vcs: Option<String>,
+    /// Files which change along with `HEAD`, empty when read from `.cargo_vcs_info.json`.
+    pub(crate) watched_paths: Vec<PathBuf>,
+}
+
+impl Head {
+    /// Reads `HEAD` of a crate placed in given directory.
+    ///
+    /// When directory contains `.cargo_vcs_info.json` (crate was unpacked from `.crate` archive) it is used,
+    /// otherwise repository that contains given directory is read.
+    ///
+    /// Returns `None` when directory isn't a part of any repository.
+    pub(crate) fn read(directory: &Path) -> Result<Option<Self>> {
+        if let Some(vcs_info) = VcsInfo::read(directory)? {
+            let head = Self {
+                commit_hash: vcs_info.commit_hash,
+                commit_datetime: None,
+                dirty: vcs_info.dirty,
+                branch: None,
+                tag: None,
+                path_in_vcs: Some(vcs_info.path_in_vcs),
+                watched_paths: Vec::new(),
+            };
+            return Ok(Some(head));
+        }
+
+        let repository = match Repository::discover(directory)? {
+            Some(repository) => repository,
+            None => return Ok(None),
+        };
+
+        // unborn branch of freshly initialized repository has no commits yet
+        let commit_hash = match repository.commit_hash()? {
+            Some(commit_hash) => commit_hash,
+            None => return Ok(None),
+        };
+        let commit_datetime = Some(repository.commit(&commit_hash)?.committer_datetime);
+        let dirty = repository.is_dirty()?;
+        let branch = repository.branch()?;
+        let tag = repository.tag(&commit_hash)?;
+        let watched_paths = repository.watched_paths()?;
+
+        let head = Self {
+            commit_hash,
+            commit_datetime,
+            dirty,
+            branch,
+            tag,
+            path_in_vcs: None,
+            watched_paths,
+        };
+
+        Ok(Some(head))
+    }
+}
+
+/// Contents of `.cargo_vcs_info.json` file created by `cargo package`.
+///
+/// Resources:
+/// * [The Cargo Book: cargo package](https://doc.rust-lang.org/cargo/commands/cargo-package.html#cargo_vcs_infojson-format).
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub(crate) struct VcsInfo {
+    pub(crate) commit_hash: String,
+    pub(crate) dirty: bool,
+    pub(crate) path_in_vcs: String,
+}
+
+impl VcsInfo {
+    const FILE_NAME: &'static str = ".cargo_vcs_info.json";
+
+    /// Reads `.cargo_vcs_info.json` from given directory.
+    ///
+    /// Returns `None` when file doesn't exist.
+    pub(crate) fn read(directory: &Path) -> Result<Option<Self>> {
+        let content = match fs::read_to_string(directory.join(Self::FILE_NAME)) {
+            Ok(content) => content,
+            Err(error) if error.kind() == io::ErrorKind::NotFound => return Ok(None),
+            Err(error) => return Err(error.into()),
+        };
+
+        Self::parse(&content).map(Some)
+    }
+
+    /// Parse `.cargo_vcs_info.json` content.
+    fn parse(content: &str) -> Result<Self> {
+        let value: Value = content.parse()?;
+        let git = value
+            .get("git")
+            .ok_or_else(|| Error::Git(format!("missing git object in {}", Self::FILE_NAME)))?;
+
+        let commit_hash = git
+            .get("sha1")
+            .and_then(Value::as_str)
+            .ok_or_else(|| Error::Git(format!("missing git.sha1 string in {}", Self::FILE_NAME)))?
+            .to_string();
+        // SHA-1 and SHA-256 object names
+        if !matches!(commit_hash.len(), 40 | 64) || !commit_hash.bytes().all(|byte| byte.is_ascii_hexdigit()) {
+            return Err(Error::Git(format!(
+                "invalid git.sha1 {commit_hash:?} in {}",
+                Self::FILE_NAME
+            )));
+        }
+        let dirty = git.get("dirty").and_then(Value::as_bool).unwrap_or(false);
+        let path_in_vcs = value
+            .get("path_in_vcs")
+            .and_then(Value::as_str)
+            .unwrap_or_default()
+            .to_string();
+
+        let vcs_info = Self {
+            commit_hash,
+            dirty,
+            path_in_vcs,
+        };
+
+        Ok(vcs_info)
+    }
+}
+
+/// A Git object kind.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+enum Kind {
+    Commit,
+    Tree,
+    Blob,
+    Tag,
+}
+
+impl Kind {
+    const BLOB_STR: &'static str = "blob";
+    const COMMIT_STR: &'static str = "commit";
+    const TAG_STR: &'static str = "tag";
+    const TREE_STR: &'static str = "tree";
+
+    /// Parse kind from loose object header.
+    fn from_name(name: &[u8]) -> Result<Self> {
+        match name {
+            name if name == Self::COMMIT_STR.as_bytes() => Ok(Self::Commit),
+            name if name == Self::TREE_STR.as_bytes() => Ok(Self::Tree),
+            name if name == Self::BLOB_STR.as_bytes() => Ok(Self::Blob),
+            name if name == Self::TAG_STR.as_bytes() => Ok(Self::Tag),
+            _ => Err(Error::Git(String::from("unknown object kind"))),
+        }
+    }
+
+    /// Parse kind from packed object header.
+    fn from_pack_type(pack_type: u8) -> Result<Self> {
+        match pack_type {
+            1 => Ok(Self::Commit),
+            2 => Ok(Self::Tree),
+            3 => Ok(Self::Blob),
+            4 => Ok(Self::Tag),
+            _ => Err(Error::Git(format!("unknown packed object type {pack_type}"))),
+        }
+    }
+}
+
+/// A Git object.
+#[derive(Clone, Debug, Eq, PartialEq)]
+struct Object {
+    kind: Kind,
+    data: Vec<u8>,
+}
+
+/// A commit object.
+#[derive(Clone, Debug, Eq, PartialEq)]
+struct Commit {
+    tree: String,
+    parents: Vec<String>,
+    committer_datetime: DateTime<FixedOffset>,
+}
+
+impl Commit {
+    /// Parse commit object.
+    fn parse(data: &[u8]) -> Result<Self> {
+        let data = String::from_utf8_lossy(data);
+
+        let mut tree = None;
+        let mut parents = Vec::new();
+        let mut committer_datetime = None;
+        for line in data.lines().take_while(|line| !line.is_empty()) {
+            if let Some(hash) = line.strip_prefix("tree ") {
+                tree = Some(hash.to_string());
+            } else if let Some(parent) = line.strip_prefix("parent ") {
+                parents.push(parent.to_string());
+            } else if let Some(committer) = line.strip_prefix("committer ") {
+                committer_datetime = Some(Self::parse_datetime(committer)?);
+            }
+        }
+
+        let tree = tree.ok_or_else(|| Error::Git(String::from("commit without tree")))?;
+        let committer_datetime =
+            committer_datetime.ok_or_else(|| Error::Git(String::from("commit without committer")))?;
+
+        let commit = Self {
+            tree,
+            parents,
+            committer_datetime,
+        };
+
+        Ok(commit)
+    }
+
+    /// Parse datetime from `Name <email> timestamp offset` signature.
+    fn parse_datetime(signature: &str) -> Result<DateTime<FixedOffset>> {
+        let error = || Error::Git(format!("invalid signature {signature:?}"));
+
+        let mut fields = signature.rsplitn(3, ' ');
+        let offset = fields.next().ok_or_else(error)?;
+        let timestamp = fields.next().ok_or_else(error)?;
+
+        let timestamp: i64 = timestamp.parse().map_err(|_| error())?;
+        let (sign, offset) = match offset.split_at(1) {
+            ("+", offset) => (1, offset),
+            ("-", offset) => (-1, offset),
+            _ => return Err(error()),
+        };
+        if offset.len() != 4 {
+            return Err(error());
+        }
+        let hours: i32 = offset[..2].parse().map_err(|_| error())?;
+        let minutes: i32 = offset[2..].parse().map_err(|_| error())?;
+
+        let offset = FixedOffset::east_opt(sign * (hours * 3600 + minutes * 60)).ok_or_else(error)?;
+        offset.timestamp_opt(timestamp, 0).single().ok_or_else(error)
+    }
+}
+
+/// Modes and hashes of files by their paths.
+type TreeEntries = BTreeMap<Vec<u8>, (u32, Vec<u8>)>;
+
+/// An entry of index file.
+#[derive(Clone, Debug, Eq, PartialEq)]
+struct IndexEntry {
+    path: Vec<u8>,
+    mtime_seconds: u32,
+    mtime_nanoseconds: u32,
+    mode: u32,
+    size: u32,
+    hash: Vec<u8>,
+    flags: u16,
+    extended_flags: u16,
+}
+
+impl IndexEntry {
+    /// Checks whether entry is assume-valid, skip-worktree or gitlink one, working tree file of such entry isn't
+    /// compared with index.
+    fn is_ignored(&self) -> bool {
+        self.flags & 0x8000 != 0 || self.extended_flags & 0x4000 != 0 || self.mode >> 12 == 0b1110
+    }
+}
+
+/// An entry of `packed-refs` file.
+#[derive(Clone, Debug, Eq, PartialEq)]
+struct PackedReference {
+    name: String,
+    hash: String,
+    peeled: Option<String>,
+}
+
+/// A pack file along with its index.
+#[derive(Clone, Debug, Eq, PartialEq)]
+struct Pack {
+    path: PathBuf,
+    hash_length: usize,
+    hashes: Vec<u8>,
+    offsets: Vec<u64>,
+    sorted_offsets: Vec<u64>,
+    length: u64,
+}
+
+impl Pack {
+    const INDEX_SIGNATURE: [u8; 4] = [0xFF, 0x74, 0x4F, 0x63];
+
+    /// Loads all packs from given directory.
+    fn load_all(directory: &Path, hash_length: usize) -> Result<Vec<Self>> {
+        let entries = match fs::read_dir(directory) {
+            Ok(entries) => entries,
+            Err(error) if error.kind() == io::ErrorKind::NotFound => return Ok(Vec::new()),
+            Err(error) => return Err(error.into()),
+        };
+
+        let mut packs = Vec::new();
+        for entry in entries {
+            let path = entry?.path();
+            if path.extension().map_or(false, |extension| extension == "idx") {
+                packs.push(Self::load(&path, hash_length)?);
+            }
+        }
+
+        Ok(packs)
+    }
+
+    /// Loads version 2 pack index.
+    fn load(index_path: &Path, hash_length: usize) -> Result<Self> {
+        let index = fs::read(index_path)?;
+        let error = || Error::Git(format!("invalid pack index {}", index_path.display()));
+
+        if index.get(..4) != Some(&Self::INDEX_SIGNATURE[..]) || read_u32(&index, 4) != Some(2) {
+            return Err(error());
+        }
+        let count = read_u32(&index, 8 + 255 * 4).ok_or_else(error)? as usize;
+
+        let hashes_start = 8 + 256 * 4;
+        let offsets_start = hashes_start + count * (hash_length + 4);
+        let large_offsets_start = offsets_start + count * 4;
+
+        let hashes = index
+            .get(hashes_start..hashes_start + count * hash_length)
+            .ok_or_else(error)?;
+        let offsets = (0..count)
+            .map(|position| {
+                let offset = read_u32(&index, offsets_start + position * 4).ok_or_else(error)?;
+                if offset & 0x8000_0000 == 0 {
+                    Ok(u64::from(offset))
+                } else {
+                    let position = large_offsets_start + (offset & 0x7FFF_FFFF) as usize * 8;
+                    read_u64(&index, position).ok_or_else(error)
+                }
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        let mut sorted_offsets = offsets.clone();
+        sorted_offsets.sort_unstable();
+
+        let path = index_path.with_extension("pack");
+        let length = fs::metadata(&path)?.len();
+
+        let pack = Self {
+            path,
+            hash_length,
+            hashes: hashes.to_vec(),
+            offsets,
+            sorted_offsets,
+            length,
+        };
+
+        Ok(pack)
+    }
+
+    /// Returns offset of an object with given hash.
+    fn find(&self, hash: &[u8]) -> Option<u64> {
+        let count = self.offsets.len();
+        let (mut low, mut high) = (0, count);
+        while low < high {
+            let middle = (low + high) / 2;
+            let candidate = &self.hashes[middle * self.hash_length..(middle + 1) * self.hash_length];
+            match candidate.cmp(hash) {
+                std::cmp::Ordering::Less => low = middle + 1,
+                std::cmp::Ordering::Greater => high = middle,
+                std::cmp::Ordering::Equal => return Some(self.offsets[middle]),
+            }
+        }
+        None
+    }
+
+    /// Reads raw entry starting at given offset.
+    fn read_entry(&self, offset: u64) -> Result<Vec<u8>> {
+        let end = match self.sorted_offsets.binary_search(&offset) {
+            Ok(position) => {
+                self.sorted_offsets
+                    .get(position + 1)
+                    .copied()
+                    .unwrap_or_else(|| self.length.saturating_sub(self.hash_length as u64))
+            },
+            Err(_) => return Err(Error::Git(format!("invalid pack offset {offset}"))),
+        };
+
+        let mut file = File::open(&self.path)?;
+        file.seek(SeekFrom::Start(offset))?;
+        let mut entry = Vec::new();
+        file.take(end.saturating_sub(offset)).read_to_end(&mut entry)?;
+
+        Ok(entry)
+    }
+}
+
+/// A Git repository queried by build script.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub(crate) struct Repository {
+    git_directory: PathBuf,
+    common_directory: PathBuf,
+    work_tree: PathBuf,
+    hash_length: usize,
+    packs: Vec<Pack>,
+}
+
+impl Repository {
+    const MAX_DEPTH: usize = 16;
+    const MAX_TAG_DISTANCE: usize = 10_000;
+
+    /// Finds a repository that contains given directory.
+    ///
+    /// Both regular repositories and linked worktrees (where `.git` is a file with `gitdir` path) are supported.
+    pub(crate) fn discover(directory: &Path) -> Result<Option<Self>> {
+        for work_tree in directory.ancestors() {
+            let dot_git = work_tree.join(".git");
+            let git_directory = if dot_git.is_dir() {
+                dot_git
+            } else if dot_git.is_file() {
+                let content = fs::read_to_string(&dot_git)?;
+                let git_directory = content
+                    .trim()
+                    .strip_prefix("gitdir:")
+                    .ok_or_else(|| Error::Git(format!("invalid gitdir file {}", dot_git.display())))?;
+                work_tree.join(git_directory.trim())
+            } else {
+                continue;
+            };
+
+            return Self::open(git_directory, work_tree.to_path_buf()).map(Some);
+        }
+
+        Ok(None)
+    }
+
+    /// Opens repository with given Git directory.
+    fn open(git_directory: PathBuf, work_tree: PathBuf) -> Result<Self> {
+        let common_directory = match fs::read_to_string(git_directory.join("commondir")) {
+            Ok(common_directory) => fs::canonicalize(git_directory.join(common_directory.trim()))?,
+            Err(error) if error.kind() == io::ErrorKind::NotFound => git_directory.clone(),
+            Err(error) => return Err(error.into()),
+        };
+
+        let hash_length = match fs::read_to_string(common_directory.join("config")) {
+            Ok(config) if Self::is_sha256(&config) => 32,
+            Ok(_) => 20,
+            Err(error) if error.kind() == io::ErrorKind::NotFound => 20,
+            Err(error) => return Err(error.into()),
+        };
+
+        let packs = Pack::load_all(&common_directory.join("objects").join("pack"), hash_length)?;
+
+        let repository = Self {
+            git_directory,
+            common_directory,
+            work_tree,
+            hash_length,
+            packs,
+        };
+
+        Ok(repository)
+    }
+
+    /// Checks whether config sets `extensions.objectFormat` to `sha256`.
+    fn is_sha256(config: &str) -> bool {
+        config.lines().any(|line| {
+            let line: String = line.chars().filter(|char| !char.is_whitespace()).collect();
+            line.eq_ignore_ascii_case("objectformat=sha256")
+        })
+    }
+
+    /// Returns full hash of `HEAD` commit or `None` when `HEAD` points to unborn branch.
+    pub(crate) fn commit_hash(&self) -> Result<Option<String>> {
+        self.resolve("HEAD")
+    }
+
+    /// Returns name of checked out branch or `None` when `HEAD` is detached.
+    pub(crate) fn branch(&self) -> Result<Option<String>> {
+        let head = self
+            .read_loose_reference("HEAD")?
+            .ok_or_else(|| Error::Git(String::from("missing HEAD")))?;
+        let branch = head
+            .strip_prefix("ref:")
+            .and_then(|reference| reference.trim().strip_prefix("refs/heads/"))
+            .map(ToString::to_string);
+        Ok(branch)
+    }
+
+    /// Returns paths which change along with `HEAD`, checked out reference, tags, index or tracked files.
+    ///
+    /// When checked out reference is packed, directory where it would be created is returned instead. Tracked files
+    /// are returned even when removed from working tree, so build script is rerun until they are restored.
+    pub(crate) fn watched_paths(&self) -> Result<Vec<PathBuf>> {
+        let mut paths = vec![self.git_directory.join("HEAD")];
+
+        let head = self.read_loose_reference("HEAD")?;
+        if let Some(reference) = head.as_deref().and_then(|head| head.strip_prefix("ref:")) {
+            let reference = reference.trim();
+            let path = [&self.git_directory, &self.common_directory]
+                .into_iter()
+                .map(|directory| directory.join(reference))
+                .find(|path| path.is_file())
+                .unwrap_or_else(|| self.common_directory.join(reference));
+            let path = path
+                .ancestors()
+                .find(|path| path.exists())
+                .filter(|path| path.starts_with(&self.common_directory) || path.starts_with(&self.git_directory))
+                .map(Path::to_path_buf);
+            paths.extend(path);
+        }
+
+        paths.push(self.common_directory.join("refs").join("tags"));
+        paths.push(self.common_directory.join("packed-refs"));
+        paths.push(self.git_directory.join("index"));
+        paths.retain(|path| path.exists());
+        paths.dedup();
+
+        let entries = self.index()?.unwrap_or_default();
+        let files = entries
+            .iter()
+            .filter(|entry| !entry.is_ignored())
+            .map(|entry| self.work_tree.join(String::from_utf8_lossy(&entry.path).as_ref()));
+        paths.extend(files);
+
+        Ok(paths)
+    }
+
+    /// Returns name of the nearest tag reachable from given commit.
+    ///
+    /// When many tags point to the same commit the greatest name is chosen. History ends at commits listed in
+    /// `shallow` file or with missing parents, at most [`Self::MAX_TAG_DISTANCE`] commits are visited.
+    pub(crate) fn tag(&self, commit_hash: &str) -> Result<Option<String>> {
+        let tags = self.tags()?;
+        if tags.is_empty() {
+            return Ok(None);
+        }
+        let shallow = self.shallow_commits()?;
+
+        let mut queue = VecDeque::new();
+        queue.push_back(commit_hash.to_string());
+        let mut visited = HashSet::new();
+        while let Some(hash) = queue.pop_front() {
+            if visited.len() >= Self::MAX_TAG_DISTANCE {
+                break;
+            }
+            if !visited.insert(hash.clone()) {
+                continue;
+            }
+            if let Some(tag) = tags.get(&hash) {
+                return Ok(Some(tag.clone()));
+            }
+            if shallow.contains(&hash) {
+                continue;
+            }
+            if let Some(commit) = self.find_commit(&hash)? {
+                let parents = commit.parents.into_iter().filter(|parent| !visited.contains(parent));
+                queue.extend(parents);
+            }
+        }
+
+        Ok(None)
+    }
+
+    /// Reads hashes of commits listed in `shallow` file, their parents aren't available.
+    fn shallow_commits(&self) -> Result<HashSet<String>> {
+        match fs::read_to_string(self.common_directory.join("shallow")) {
+            Ok(content) => Ok(content.lines().map(str::trim).map(ToString::to_string).collect()),
+            Err(error) if error.kind() == io::ErrorKind::NotFound => Ok(HashSet::new()),
+            Err(error) => Err(error.into()),
+        }
+    }
+
+    /// Checks whether index differs from `HEAD` commit or tracked files in working tree differ from index.
+    ///
+    /// Untracked files are ignored.
+    pub(crate) fn is_dirty(&self) -> Result<bool> {
+        let entries = match self.index()? {
+            Some(entries) => entries,
+            None => return Ok(false),
+        };
+
+        for entry in &entries {
+            let stage = (entry.flags >> 12) & 0b11;
+            if stage != 0 || entry.extended_flags & 0x2000 != 0 {
+                // unmerged or intent-to-add entry
+                return Ok(true);
+            }
+        }
+
+        let staged = entries
+            .iter()
+            .map(|entry| (entry.path.clone(), (entry.mode, entry.hash.clone())))
+            .collect::<BTreeMap<_, _>>();
+        let committed = match self.resolve("HEAD")? {
+            Some(hash) => self.tree_entries(&self.commit(&hash)?.tree)?,
+            None => BTreeMap::new(),
+        };
+        if staged != committed {
+            return Ok(true);
+        }
+
+        for entry in &entries {
+            if self.is_modified(entry)? {
+                return Ok(true);
+            }
+        }
+
+        Ok(false)
+    }
+
+    /// Checks whether working tree file differs from index entry.
+    fn is_modified(&self, entry: &IndexEntry) -> Result<bool> {
+        if entry.is_ignored() {
+            return Ok(false);
+        }
+
+        let path = self.work_tree.join(String::from_utf8_lossy(&entry.path).as_ref());
+        let metadata = match fs::symlink_metadata(&path) {
+            Ok(metadata) => metadata,
+            Err(error) if error.kind() == io::ErrorKind::NotFound => return Ok(true),
+            Err(error) => return Err(error.into()),
+        };
+        #[allow(clippy::cast_possible_truncation)]
+        if metadata.len() as u32 != entry.size {
+            return Ok(true);
+        }
+        let mtime = metadata.modified()?.duration_since(UNIX_EPOCH).unwrap_or_default();
+        #[allow(clippy::cast_possible_truncation)]
+        if mtime.as_secs() as u32 == entry.mtime_seconds && mtime.subsec_nanos() == entry.mtime_nanoseconds {
+            return Ok(false);
+        }
+
+        let content = if metadata.file_type().is_symlink() {
+            fs::read_link(&path)?.to_string_lossy().into_owned().into_bytes()
+        } else {
+            fs::read(&path)?
+        };
+        Ok(self.hash_length != 20 || !Self::is_blob(&content, &entry.hash))
+    }
+
+    /// Reads entries of index file.
+    ///
+    /// Returns `None` when index doesn't exist.
+    fn index(&self) -> Result<Option<Vec<IndexEntry>>> {
+        let index = match fs::read(self.git_directory.join("index")) {
+            Ok(index) => index,
+            Err(error) if error.kind() == io::ErrorKind::NotFound => return Ok(None),
+            Err(error) => return Err(error.into()),
+        };
+        let error = || Error::Git(String::from("invalid index"));
+
+        if index.get(..4) != Some(&b"DIRC"[..]) {
+            return Err(error());
+        }
+        let version = read_u32(&index, 4).ok_or_else(error)?;
+        if !(2..=4).contains(&version) {
+            return Err(Error::Git(format!("unsupported index version {version}")));
+        }
+        let count = read_u32(&index, 8).ok_or_else(error)?;
+
+        let mut entries = Vec::new();
+        let mut position = 12;
+        let mut path = Vec::new();
+        for _ in 0..count {
+            let start = position;
+            let mtime_seconds = read_u32(&index, start + 8).ok_or_else(error)?;
+            let mtime_nanoseconds = read_u32(&index, start + 12).ok_or_else(error)?;
+            let mode = read_u32(&index, start + 24).ok_or_else(error)?;
+            let size = read_u32(&index, start + 36).ok_or_else(error)?;
+            let hash = index.get(start + 40..start + 40 + self.hash_length).ok_or_else(error)?;
+            position = start + 40 + self.hash_length;
+            let flags = read_u16(&index, position).ok_or_else(error)?;
+            position += 2;
+            let extended_flags = if version >= 3 && flags & 0x4000 != 0 {
+                position += 2;
+                read_u16(&index, position - 2).ok_or_else(error)?
+            } else {
+                0
+            };
+
+            if version == 4 {
+                let strip = read_offset(&index, &mut position).ok_or_else(error)? as usize;
+                path.truncate(path.len().checked_sub(strip).ok_or_else(error)?);
+                let suffix = index.get(position..).ok_or_else(error)?;
+                let length = suffix.iter().position(|byte| *byte == 0).ok_or_else(error)?;
+                path.extend_from_slice(&suffix[..length]);
+                position += length + 1;
+            } else {
+                let name = index.get(position..).ok_or_else(error)?;
+                let length = name.iter().position(|byte| *byte == 0).ok_or_else(error)?;
+                path = name[..length].to_vec();
+                position = start + ((position - start + length + 8) & !7);
+            }
+
+            entries.push(IndexEntry {
+                path: path.clone(),
+                mtime_seconds,
+                mtime_nanoseconds,
+                mode,
+                size,
+                hash: hash.to_vec(),
+                flags,
+                extended_flags,
+            });
+        }
+
+        Ok(Some(entries))
+    }
+
+    /// Returns modes and hashes of all non-tree entries of tree, recursively, by path.
+    fn tree_entries(&self, hash: &str) -> Result<TreeEntries> {
+        let mut entries = BTreeMap::new();
+        let mut trees = vec![(hash.to_string(), Vec::new())];
+        while let Some((hash, prefix)) = trees.pop() {
+            let object = self.read_object(&hash)?;
+            if object.kind != Kind::Tree {
+                return Err(Error::Git(format!("object {hash} isn't a tree")));
+            }
+            let error = || Error::Git(format!("invalid tree {hash}"));
+
+            let mut data = object.data.as_slice();
+            while !data.is_empty() {
+                let space = data.iter().position(|byte| *byte == b' ').ok_or_else(error)?;
+                let mode = std::str::from_utf8(&data[..space]).map_err(|_| error())?;
+                let mode = u32::from_str_radix(mode, 8).map_err(|_| error())?;
+                data = &data[space + 1..];
+                let null = data.iter().position(|byte| *byte == 0).ok_or_else(error)?;
+                let mut path = prefix.clone();
+                path.extend_from_slice(&data[..null]);
+                data = &data[null + 1..];
+                let entry_hash = data.get(..self.hash_length).ok_or_else(error)?.to_vec();
+                data = &data[self.hash_length..];
+
+                if mode == 0o40000 {
+                    path.push(b'/');
+                    trees.push((encode_hex(&entry_hash), path));
+                } else {
+                    entries.insert(path, (mode, entry_hash));
+                }
+            }
+        }
+        Ok(entries)
+    }
+
+    /// Checks whether content has given SHA-1 blob hash.
+    fn is_blob(content: &[u8], hash: &[u8]) -> bool {
+        let mut sha1 = Sha1::new();
+        sha1.update(format!("blob {}\0", content.len()).as_bytes());
+        sha1.update(content);
+        sha1.digest().bytes() == hash
+    }
+
+    /// Reads and parses commit object.
+    fn commit(&self, hash: &str) -> Result<Commit> {
+        self.find_commit(hash)?
+            .ok_or_else(|| Error::Git(format!("cannot find object {hash}")))
+    }
+
+    /// Reads and parses commit object, returns `None` when object is missing.
+    fn find_commit(&self, hash: &str) -> Result<Option<Commit>> {
+        let object = match self.find_object(hash)? {
+            Some(object) => object,
+            None => return Ok(None),
+        };
+        if object.kind != Kind::Commit {
+            return Err(Error::Git(format!("object {hash} isn't a commit")));
+        }
+        Commit::parse(&object.data).map(Some)
+    }
+
+    /// Returns map of tagged commit hashes and tag names.
+    fn tags(&self) -> Result<BTreeMap<String, String>> {
+        let mut references = BTreeMap::new();
+        for reference in self.packed_references()? {
+            if let Some(name) = reference.name.strip_prefix("refs/tags/") {
+                references.insert(name.to_string(), (reference.hash, reference.peeled));
+            }
+        }
+        let mut directories = vec![(self.common_directory.join("refs").join("tags"), String::new())];
+        while let Some((directory, prefix)) = directories.pop() {
+            let entries = match fs::read_dir(&directory) {
+                Ok(entries) => entries,
+                Err(error) if error.kind() == io::ErrorKind::NotFound => continue,
+                Err(error) => return Err(error.into()),
+            };
+            for entry in entries {
+                let entry = entry?;
+                let name = format!("{prefix}{}", entry.file_name().to_string_lossy());
+                if entry.file_type()?.is_dir() {
+                    directories.push((entry.path(), format!("{name}/")));
+                } else {
+                    let hash = fs::read_to_string(entry.path())?.trim().to_string();
+                    references.insert(name, (hash, None));
+                }
+            }
+        }
+
+        let mut tags = BTreeMap::new();
+        for (name, (hash, peeled)) in references {
+            let hash = match peeled {
+                Some(peeled) => Some(peeled),
+                None => self.peel(hash)?,
+            };
+            // objects of tags outside of shallow history may be missing
+            if let Some(hash) = hash {
+                tags.insert(hash, name);
+            }
+        }
+
+        Ok(tags)
+    }
+
+    /// Follows annotated tags until non-tag object is found, returns `None` when any object is missing.
+    fn peel(&self, mut hash: String) -> Result<Option<String>> {
+        for _ in 0..Self::MAX_DEPTH {
+            let object = match self.find_object(&hash)? {
+                Some(object) => object,
+                None => return Ok(None),
+            };
+            if object.kind != Kind::Tag {
+                return Ok(Some(hash));
+            }
+            hash = String::from_utf8_lossy(&object.data)
+                .lines()
+                .find_map(|line| line.strip_prefix("object "))
+                .ok_or_else(|| Error::Git(format!("tag {hash} without object")))?
+                .to_string();
+        }
+        Err(Error::Git(String::from("too deeply nested tags")))
+    }
+
+    /// Resolves reference to object hash.
+    fn resolve(&self, name: &str) -> Result<Option<String>> {
+        let mut name = name.to_string();
+        for _ in 0..Self::MAX_DEPTH {
+            let content = match self.read_loose_reference(&name)? {
+                Some(content) => content,
+                None => {
+                    let hash = self
+                        .packed_references()?
+                        .into_iter()
+                        .find(|reference| reference.name == name)
+                        .map(|reference| reference.hash);
+                    return Ok(hash);
+                },
+            };
+            match content.strip_prefix("ref:") {
+                Some(target) => name = target.trim().to_string(),
+                None => return Ok(Some(content)),
+            }
+        }
+        Err(Error::Git(String::from("too deeply nested symbolic references")))
+    }
+
+    /// Reads loose reference from Git directory or common directory.
+    fn read_loose_reference(&self, name: &str) -> Result<Option<String>> {
+        for directory in [&self.git_directory, &self.common_directory] {
+            match fs::read_to_string(directory.join(name)) {
+                Ok(content) => return Ok(Some(content.trim().to_string())),
+                Err(error) if error.kind() == io::ErrorKind::NotFound => continue,
+                Err(error) => return Err(error.into()),
+            }
+        }
+        Ok(None)
+    }
+
+    /// Reads `packed-refs` file.
+    fn packed_references(&self) -> Result<Vec<PackedReference>> {
+        let content = match fs::read_to_string(self.common_directory.join("packed-refs")) {
+            Ok(content) => content,
+            Err(error) if error.kind() == io::ErrorKind::NotFound => return Ok(Vec::new()),
+            Err(error) => return Err(error.into()),
+        };
+
+        let mut references: Vec<PackedReference> = Vec::new();
+        for line in content.lines() {
+            if line.starts_with('#') || line.is_empty() {
+                continue;
+            }
+            if let Some(peeled) = line.strip_prefix('^') {
+                let reference = references
+                    .last_mut()
+                    .ok_or_else(|| Error::Git(String::from("invalid packed-refs")))?;
+                reference.peeled = Some(peeled.to_string());
+                continue;
+            }
+            let (hash, name) = line
+                .split_once(' ')
+                .ok_or_else(|| Error::Git(String::from("invalid packed-refs")))?;
+            references.push(PackedReference {
+                name: name.to_string(),
+                hash: hash.to_string(),
+                peeled: None,
+            });
+        }
+
+        Ok(references)
+    }
+
+    /// Reads object from loose objects or packs.
+    fn read_object(&self, hash: &str) -> Result<Object> {
+        self.find_object(hash)?
+            .ok_or_else(|| Error::Git(format!("cannot find object {hash}")))
+    }
+
+    /// Reads object from loose objects or packs, returns `None` when object is missing.
+    fn find_object(&self, hash: &str) -> Result<Option<Object>> {
+        if let Some(object) = self.read_loose_object(hash)? {
+            return Ok(Some(object));
+        }
+
+        let bytes = decode_hex(hash).ok_or_else(|| Error::Git(format!("invalid object hash {hash}")))?;
+        for pack in &self.packs {
+            if let Some(offset) = pack.find(&bytes) {
+                return self.read_packed_object(pack, offset, 0).map(Some);
+            }
+        }
+
+        Ok(None)
+    }
+
+    /// Reads loose object.
+    fn read_loose_object(&self, hash: &str) -> Result<Option<Object>> {
+        if hash.len() < 3 || !hash.is_ascii() {
+            return Err(Error::Git(format!("invalid object hash {hash}")));
+        }
+        let path = self.common_directory.join("objects").join(&hash[..2]).join(&hash[2..]);
+        let compressed = match fs::read(path) {
+            Ok(compressed) => compressed,
+            Err(error) if error.kind() == io::ErrorKind::NotFound => return Ok(None),
+            Err(error) => return Err(error.into()),
+        };
+
+        let data = inflate(&compressed)?;
+        let error = || Error::Git(format!("invalid loose object {hash}"));
+        let header_length = data.iter().position(|byte| *byte == 0).ok_or_else(error)?;
+        let kind = data[..header_length]
+            .split(|byte| *byte == b' ')
+            .next()
+            .ok_or_else(error)?;
+        let kind = Kind::from_name(kind)?;
+
+        let object = Object {
+            kind,
+            data: data[header_length + 1..].to_vec(),
+        };
+
+        Ok(Some(object))
+    }
+
+    /// Reads packed object and resolves deltas.
+    fn read_packed_object(&self, pack: &Pack, offset: u64, depth: usize) -> Result<Object> {
+        if depth > Self::MAX_DEPTH * 4 {
+            return Err(Error::Git(String::from("too long delta chain")));
+        }
+
+        let entry = pack.read_entry(offset)?;
+        let error = || Error::Git(format!("invalid pack entry at {offset}"));
+
+        let mut position = 0;
+        let mut byte = *entry.first().ok_or_else(error)?;
+        let pack_type = (byte >> 4) & 0b111;
+        while byte & 0x80 != 0 {
+            position += 1;
+            byte = *entry.get(position).ok_or_else(error)?;
+        }
+        position += 1;
+
+        match pack_type {
+            6 => {
+                let distance = read_offset(&entry, &mut position).ok_or_else(error)?;
+                let base_offset = offset.checked_sub(distance).ok_or_else(error)?;
+                let base = self.read_packed_object(pack, base_offset, depth + 1)?;
+                let delta = inflate(entry.get(position..).ok_or_else(error)?)?;
+                let data = apply_delta(&base.data, &delta).ok_or_else(error)?;
+                Ok(Object { kind: base.kind, data })
+            },
+            7 => {
+                let base_hash = entry.get(position..position + pack.hash_length).ok_or_else(error)?;
+                let base = self.read_object(&encode_hex(base_hash))?;
+                let delta = inflate(&entry[position + pack.hash_length..])?;
+                let data = apply_delta(&base.data, &delta).ok_or_else(error)?;
+                Ok(Object { kind: base.kind, data })
+            },
+            pack_type => {
+                let kind = Kind::from_pack_type(pack_type)?;
+                let data = inflate(&entry[position..])?;
+                Ok(Object { kind, data })
+            },
+        }
+    }
+}
+
+/// Decompresses zlib stream.
+fn inflate(compressed: &[u8]) -> Result<Vec<u8>> {
+    decompress_to_vec_zlib(compressed).map_err(|error| Error::Git(format!("cannot decompress object: {error}")))
+}
+
+/// Applies delta instructions to base object.
+fn apply_delta(base: &[u8], delta: &[u8]) -> Option<Vec<u8>> {
+    let mut position = 0;
+    let base_size = read_size(delta, &mut position)?;
+    let target_size = read_size(delta, &mut position)?;
+    if base_size != base.len() {
+        return None;
+    }
+
+    let mut target = Vec::with_capacity(target_size);
+    while let Some(&instruction) = delta.get(position) {
+        position += 1;
+        if instruction & 0x80 != 0 {
+            let mut offset = 0;
+            for shift in 0..4 {
+                if instruction & (1 << shift) != 0 {
+                    offset |= usize::from(*delta.get(position)?) << (8 * shift);
+                    position += 1;
+                }
+            }
+            let mut size = 0;
+            for shift in 0..3 {
+                if instruction & (0x10 << shift) != 0 {
+                    size |= usize::from(*delta.get(position)?) << (8 * shift);
+                    position += 1;
+                }
+            }
+            if size == 0 {
+                size = 0x10000;
+            }
+            target.extend_from_slice(base.get(offset..offset.checked_add(size)?)?);
+        } else if instruction != 0 {
+            let size = usize::from(instruction);
+            target.extend_from_slice(delta.get(position..position + size)?);
+            position += size;
+        } else {
+            return None;
+        }
+    }
+
+    (target.len() == target_size).then(|| target)
+}
+
+/// Reads little-endian base-128 size used by delta header.
+fn read_size(data: &[u8], position: &mut usize) -> Option<usize> {
+    let mut size = 0;
+    let mut shift = 0;
+    loop {
+        let byte = *data.get(*position)?;
+        *position += 1;
+        size |= usize::from(byte & 0x7F).checked_shl(shift)?;
+        shift += 7;
+        if byte & 0x80 == 0 {
+            return Some(size);
+        }
+    }
+}
+
+/// Reads big-endian base-128 offset used by offset deltas and version 4 index.
+fn read_offset(data: &[u8], position: &mut usize) -> Option<u64> {
+    let mut byte = *data.get(*position)?;
+    *position += 1;
+    let mut offset = u64::from(byte & 0x7F);
+    while byte & 0x80 != 0 {
+        byte = *data.get(*position)?;
+        *position += 1;
+        offset = (offset + 1).checked_shl(7)? | u64::from(byte & 0x7F);
+    }
+    Some(offset)
+}
+
+fn read_u16(data: &[u8], position: usize) -> Option<u16> {
+    let bytes = data.get(position..position + 2)?;
+    Some(u16::from_be_bytes([bytes[0], bytes[1]]))
+}
+
+fn read_u32(data: &[u8], position: usize) -> Option<u32> {
+    let bytes = data.get(position..position + 4)?;
+    Some(u32::from_be_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]))
+}
+
+fn read_u64(data: &[u8], position: usize) -> Option<u64> {
+    let high = read_u32(data, position)?;
+    let low = read_u32(data, position + 4)?;
+    Some(u64::from(high) << 32 | u64::from(low))
+}
+
+fn decode_hex(hex: &str) -> Option<Vec<u8>> {
+    if hex.len() % 2 != 0 || !hex.is_ascii() {
+        return None;
+    }
+    (0..hex.len())
+        .step_by(2)
+        .map(|position| u8::from_str_radix(&hex[position..position + 2], 16).ok())
+        .collect()
+}
+
+fn encode_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|byte| format!("{byte:02x}")).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::process::{Command, Output};
+
+    use super::*;
+
+    fn git(directory: &Path, args: &[&str]) -> io::Result<Output> {
+        let output = Command::new("git")
+            .args(args)
+            .current_dir(directory)
+            .env("GIT_AUTHOR_NAME", "chksum")
+            .env("GIT_AUTHOR_EMAIL", "chksum@example.com")
+            .env("GIT_COMMITTER_NAME", "chksum")
+            .env("GIT_COMMITTER_EMAIL", "chksum@example.com")
+            .env("GIT_COMMITTER_DATE", "2023-01-02T10:20:30+01:00")
+            .output()?;
+        assert!(output.status.success(), "git {args:?}: {output:?}");
+        Ok(output)
+    }
+
+    fn rev_parse(directory: &Path, revision: &str) -> Result<String> {
+        let output = git(directory, &["rev-parse", revision])?;
+        Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
+    }
+
+    fn init(directory: &Path) -> Result<()> {
+        git(directory, &["init", "--quiet"])?;
+        git(directory, &["symbolic-ref", "HEAD", "refs/heads/main"])?;
+        git(directory, &["config", "core.autocrlf", "false"])?;
+        for content in ["first\n", "first\nsecond\n", "first\nsecond\nthird\n"] {
+            fs::write(directory.join("file.txt"), content)?;
+            git(directory, &["add", "file.txt"])?;
+            git(directory, &["commit", "--quiet", "--message", content])?;
+            if content == "first\n" {
+                git(directory, &["tag", "--annotate", "--message", "v0.1.0", "v0.1.0"])?;
+            }
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_commit_parse_datetime() -> Result<()> {
+        assert_eq!(
+            Commit::parse_datetime("chksum <chksum@example.com> 1672651230 +0100")?.to_rfc3339(),
+            "2023-01-02T10:20:30+01:00"
+        );
+        assert_eq!(
+            Commit::parse_datetime("chksum <chksum@example.com> 1672651230 -0030")?.to_rfc3339(),
+            "2023-01-02T08:50:30-00:30"
+        );
+        assert!(Commit::parse_datetime("chksum <chksum@example.com> 1672651230").is_err());
+        assert!(Commit::parse_datetime("chksum <chksum@example.com> 1672651230 0100").is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_apply_delta() {
+        // copy 5 bytes from offset 0, insert 3 bytes
+        let delta = [11, 8, 0x90, 5, 3, b'a', b'b', b'c'];
+        assert_eq!(apply_delta(b"hello world", &delta), Some(b"helloabc".to_vec()));
+        assert_eq!(apply_delta(b"hello", &delta), None);
+    }
+
+    #[test]
+    fn test_head_read_loose() -> Result<()> {
+        let directory = tempfile::tempdir()?;
+        assert_eq!(Head::read(directory.path())?, None);
+
+        init(directory.path())?;
+        let head = Head::read(directory.path())?.unwrap();
+        assert_eq!(head.commit_hash, rev_parse(directory.path(), "HEAD")?);
+        assert_eq!(
+            head.commit_datetime.map(|datetime| datetime.to_rfc3339()).as_deref(),
+            Some("2023-01-02T10:20:30+01:00")
+        );
+        assert!(!head.dirty);
+        assert_eq!(head.branch.as_deref(), Some("main"));
+        assert_eq!(head.tag.as_deref(), Some("v0.1.0"));
+        Ok(())
+    }
+
+    #[test]
+    fn test_head_read_unborn() -> Result<()> {
+        let directory = tempfile::tempdir()?;
+        git(directory.path(), &["init", "--quiet"])?;
+        assert_eq!(Head::read(directory.path())?, None);
+
+        fs::write(directory.path().join("file.txt"), "first\n")?;
+        git(directory.path(), &["add", "file.txt"])?;
+        assert_eq!(Head::read(directory.path())?, None);
+        Ok(())
+    }
+
+    #[test]
+    fn test_head_read_vcs_info() -> Result<()> {
+        let directory = tempfile::tempdir()?;
+        init(directory.path())?;
+        let crate_directory = directory.path().join("crate");
+        fs::create_dir(&crate_directory)?;
+        fs::write(
+            crate_directory.join(".cargo_vcs_info.json"),
+            "{\n  \"git\": {\n    \"sha1\": \"aac20b6e7e543e6dd4118b246c77225e3a3a1302\",\n    \"dirty\": true\n  \
+             },\n  \"path_in_vcs\": \"crate\"\n}",
+        )?;
+
+        let head = Head::read(&crate_directory)?.unwrap();
+        assert_eq!(
+            head,
+            Head {
+                commit_hash: String::from("aac20b6e7e543e6dd4118b246c77225e3a3a1302"),
+                commit_datetime: None,
+                dirty: true,
+                branch: None,
+                tag: None,
+                path_in_vcs: Some(String::from("crate")),
+                watched_paths: Vec::new(),
+            }
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_vcs_info_parse() -> Result<()> {
+        assert_eq!(
+            VcsInfo::parse(r#"{"git":{"sha1":"aac20b6e7e543e6dd4118b246c77225e3a3a1302"},"path_in_vcs":""}"#)?,
+            VcsInfo {
+                commit_hash: String::from("aac20b6e7e543e6dd4118b246c77225e3a3a1302"),
+                dirty: false,
+                path_in_vcs: String::new(),
+            }
+        );
+        assert!(VcsInfo::parse(r#"{"path_in_vcs":""}"#).is_err());
+        assert!(VcsInfo::parse(r#"{"git":{"sha1":true}}"#).is_err());
+        assert!(VcsInfo::parse(r#"{"git":{"sha1":"aac20b6"}}"#).is_err());
+        assert!(VcsInfo::parse(r#"{"git":{"sha1":"zac20b6e7e543e6dd4118b246c77225e3a3a1302"}}"#).is_err());
+        assert!(VcsInfo::parse(r#"{"git":{"sha1":"ążć20b6e7e543e6dd4118b246c77225e3a3a13"}}"#).is_err());
+        assert!(VcsInfo::parse("{").is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_head_read_packed() -> Result<()> {
+        let directory = tempfile::tempdir()?;
+        init(directory.path())?;
+        git(directory.path(), &["tag", "v0.2.0"])?;
+        git(directory.path(), &["gc", "--quiet", "--aggressive"])?;
+        assert!(!directory
+            .path()
+            .join(".git")
+            .join("refs")
+            .join("tags")
+            .join("v0.2.0")
+            .exists());
+
+        let head = Head::read(directory.path())?.unwrap();
+        assert_eq!(head.commit_hash, rev_parse(directory.path(), "HEAD")?);
+        assert_eq!(head.tag.as_deref(), Some("v0.2.0"));
+
+        let repository = Repository::discover(directory.path())?.unwrap();
+        let object = repository.read_object(&rev_parse(directory.path(), "HEAD:file.txt")?)?;
+        assert_eq!(object.kind, Kind::Blob);
+        assert_eq!(object.data, b"first\nsecond\nthird\n");
+        Ok(())
+    }
+
+    #[test]
+    fn test_head_read_detached_worktree() -> Result<()> {
+        let directory = tempfile::tempdir()?;
+        let repository = directory.path().join("repository");
+        let worktree = directory.path().join("worktree");
+        fs::create_dir(&repository)?;
+        init(&repository)?;
+        git(
+            &repository,
+            &[
+                "worktree",
+                "add",
+                "--quiet",
+                "--detach",
+                worktree.to_str().unwrap(),
+                "HEAD~1",
+            ],
+        )?;
+
+        let head = Head::read(&worktree.join("subdirectory"))?.unwrap();
+        assert_eq!(head.commit_hash, rev_parse(&repository, "HEAD~1")?);
+        assert_eq!(head.branch, None);
+        assert_eq!(head.tag.as_deref(), Some("v0.1.0"));
+        Ok(())
+    }
+
+    #[test]
+    fn test_repository_is_dirty() -> Result<()> {
+        let directory = tempfile::tempdir()?;
+        init(directory.path())?;
+        let repository = Repository::discover(directory.path())?.unwrap();
+        let path = directory.path().join("file.txt");
+
+        fs::write(directory.path().join("untracked.txt"), "untracked\n")?;
+        assert!(!repository.is_dirty()?);
+
+        fs::write(&path, "first\nsecond\nthird\n")?;
+        assert!(!repository.is_dirty()?);
+
+        fs::write(&path, "first\nsecond\nTHIRD\n")?;
+        assert!(repository.is_dirty()?);
+
+        fs::remove_file(&path)?;
+        assert!(repository.is_dirty()?);
+        Ok(())
+    }
+
+    #[test]
+    fn test_repository_watched_paths() -> Result<()> {
+        let directory = tempfile::tempdir()?;
+        init(directory.path())?;
+        let git_directory = directory.path().join(".git");
+
+        let repository = Repository::discover(directory.path())?.unwrap();
+        assert_eq!(
+            repository.watched_paths()?,
+            [
+                git_directory.join("HEAD"),
+                git_directory.join("refs/heads/main"),
+                git_directory.join("refs/tags"),
+                git_directory.join("index"),
+                directory.path().join("file.txt"),
+            ]
+        );
+
+        git(directory.path(), &["pack-refs", "--all"])?;
+        assert_eq!(
+            repository.watched_paths()?,
+            [
+                git_directory.join("HEAD"),
+                git_directory.join("refs/heads"),
+                git_directory.join("refs/tags"),
+                git_directory.join("packed-refs"),
+                git_directory.join("index"),
+                directory.path().join("file.txt"),
+            ]
+        );
+
+        let worktree = directory.path().join("worktree");
+        git(
+            directory.path(),
+            &[
+                "worktree",
+                "add",
+                "--quiet",
+                "-b",
+                "feature",
+                worktree.to_str().unwrap(),
+            ],
+        )?;
+        let repository = Repository::discover(&worktree)?.unwrap();
+        let worktree_git_directory = git_directory.join("worktrees/worktree");
+        assert_eq!(
+            repository.watched_paths()?,
+            [
+                worktree_git_directory.join("HEAD"),
+                git_directory.join("refs/heads/feature"),
+                git_directory.join("refs/tags"),
+                git_directory.join("packed-refs"),
+                worktree_git_directory.join("index"),
+                worktree.join("file.txt"),
+            ]
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_repository_is_dirty_staged() -> Result<()> {
+        let directory = tempfile::tempdir()?;
+        init(directory.path())?;
+        let repository = Repository::discover(directory.path())?.unwrap();
+
+        fs::write(directory.path().join("file.txt"), "first\nsecond\nTHIRD\n")?;
+        git(directory.path(), &["add", "file.txt"])?;
+        assert!(repository.is_dirty()?);
+
+        git(directory.path(), &["reset", "--quiet", "--hard"])?;
+        assert!(!repository.is_dirty()?);
+
+        fs::write(directory.path().join("new.txt"), "new\n")?;
+        git(directory.path(), &["add", "new.txt"])?;
+        assert!(repository.is_dirty()?);
+
+        git(directory.path(), &["commit", "--quiet", "--message", "new"])?;
+        assert!(!repository.is_dirty()?);
+
+        git(directory.path(), &["rm", "--quiet", "--cached", "new.txt"])?;
+        assert!(repository.is_dirty()?);
+        Ok(())
+    }
+
+    #[test]
+    fn test_head_read_shallow() -> Result<()> {
+        let directory = tempfile::tempdir()?;
+        let repository = directory.path().join("repository");
+        let clone = directory.path().join("clone");
+        fs::create_dir(&repository)?;
+        init(&repository)?;
+        git(&repository, &["tag", "v0.2.0", "HEAD~1"])?;
+        let url = format!("file://{}", repository.display());
+        git(
+            directory.path(),
+            &[
+                "clone",
+                "--quiet",
+                "--depth",
+                "1",
+                "--no-tags",
+                &url,
+                clone.to_str().unwrap(),
+            ],
+        )?;
+        git(&clone, &["fetch", "--quiet", "--depth", "1", "origin", "tag", "v0.1.0"])?;
+
+        let head = Head::read(&clone)?.unwrap();
+        assert_eq!(head.commit_hash, rev_parse(&repository, "HEAD")?);
+        assert_eq!(head.tag, None);
+
+        // history without shallow file ends at missing parent
+        fs::remove_file(clone.join(".git").join("shallow"))?;
+        let head = Head::read(&clone)?.unwrap();
+        assert_eq!(head.tag, None);
+        Ok(())
+    }
+}
@@ -1,15 +1,22 @@
 //! Build information required items.
 
-use chrono::NaiveDateTime;
+use std::borrow::Cow;
 
+use chrono::{DateTime, FixedOffset, NaiveDate, NaiveDateTime, NaiveTime, Offset, ParseError, TimeZone, Utc};
+
+use crate::build::DatetimeSource;
 use crate::cargo::Profile;
-use crate::rust::Channel;
+use crate::package::PackageVersion;
+use crate::rust::{Channel, ChannelVersion, Host};
+use crate::target::{Endian, Panic};
 
 /// Creates a [`BuildInfo`] from environment variables.
 ///
-/// # Panics
+/// [`Package`] is read from `CARGO_PKG_*` variables of crate where macro is used, regardless of prefix. Sections
+/// disabled in build script are `None`.
 ///
-/// Panics when compile time environment variables aren't set.
+/// Environment variables with custom prefix configured by `BuildScript::prefix` can be read with `prefix = "..."`
+/// argument.
 ///
 /// # Examples
 ///
@@ -19,91 +26,386 @@ use crate::rust::Channel;
 ///
 /// # fn wrapper() -> Result<()> {
 /// let build_info = build_info!();
+/// let myapp_build_info = build_info!(prefix = "MYAPP");
 /// # }
 /// ```
 #[allow(clippy::module_name_repetitions)]
 #[macro_export]
 macro_rules! build_info {
-    () => {{
+    () => {
+        $crate::build_info!(prefix = "CHKSUM_BUILD_INFO")
+    };
+    (prefix = $prefix:literal $(,)?) => {{
         macro_rules! build {
             () => {{
-                let datetime = {
-                    let datetime = env!("CHKSUM_BUILD_INFO_BUILD_DATETIME");
-                    ::chrono::NaiveDateTime::parse_from_str(&datetime, "%Y-%m-%d %H:%M:%S")?
-                };
+                match (
+                    option_env!(concat!($prefix, "_BUILD_DATETIME")),
+                    option_env!(concat!($prefix, "_BUILD_DATETIME_SOURCE")),
+                ) {
+                    (Some(datetime), Some(datetime_source)) => {
+                        use ::std::str::FromStr;
+
+                        let datetime = $crate::Build::parse_datetime(datetime)?;
+                        let datetime_source = $crate::DatetimeSource::from_str(datetime_source)?;
 
-                ::chksum_build::Build::new(datetime)
+                        Some($crate::Build::new(datetime, datetime_source))
+                    },
+                    _ => None,
+                }
             }};
         }
 
         macro_rules! cargo {
             () => {{
-                let profile = {
-                    use ::std::str::FromStr;
+                match (
+                    option_env!(concat!($prefix, "_CARGO_PROFILE")),
+                    option_env!(concat!($prefix, "_CARGO_OPT_LEVEL")),
+                    option_env!(concat!($prefix, "_CARGO_DEBUG")),
+                    option_env!(concat!($prefix, "_CARGO_DEBUG_ASSERTIONS")),
+                    option_env!(concat!($prefix, "_CARGO_FEATURES")),
+                ) {
+                    (Some(profile), Some(opt_level), Some(debug), Some(debug_assertions), Some(features)) => {
+                        use ::std::str::FromStr;
 
-                    let profile = env!("CHKSUM_BUILD_INFO_CARGO_PROFILE");
-                    ::chksum_build::cargo::Profile::from_str(profile)?
-                };
+                        let profile = $crate::Profile::from_str(profile)?;
+                        let features = features
+                            .split(',')
+                            .filter(|feature| !feature.is_empty())
+                            .map(::std::borrow::Cow::Borrowed)
+                            .collect::<::std::vec::Vec<_>>();
 
-                ::chksum_build::Cargo::new(profile)
+                        Some($crate::Cargo::new(
+                            profile,
+                            ::std::borrow::Cow::Borrowed(opt_level),
+                            debug == "true",
+                            debug_assertions == "true",
+                            ::std::borrow::Cow::Owned(features),
+                        ))
+                    },
+                    _ => None,
+                }
             }};
         }
 
         macro_rules! rust {
             () => {{
-                let channel = {
-                    use ::std::str::FromStr;
+                match option_env!(concat!($prefix, "_RUST_CHANNEL")) {
+                    Some(channel) => {
+                        use ::std::str::FromStr;
+
+                        let channel = $crate::Channel::from_str(channel)?;
+                        let version = match option_env!(concat!($prefix, "_RUST_VERSION")) {
+                            Some(version) => Some($crate::ChannelVersion::from_str(version)?),
+                            None => None,
+                        };
+                        let commit_hash = option_env!(concat!($prefix, "_RUST_COMMIT_HASH"));
+                        let commit_date = match option_env!(concat!($prefix, "_RUST_COMMIT_DATE")) {
+                            Some(commit_date) => Some($crate::Rust::parse_commit_date(commit_date)?),
+                            None => None,
+                        };
+                        let llvm_version = option_env!(concat!($prefix, "_RUST_LLVM_VERSION"));
+
+                        Some($crate::Rust::new(
+                            channel,
+                            version,
+                            commit_hash.map(::std::borrow::Cow::Borrowed),
+                            commit_date,
+                            llvm_version.map(::std::borrow::Cow::Borrowed),
+                        ))
+                    },
+                    None => None,
+                }
+            }};
+        }
+
+        macro_rules! target {
+            () => {{
+                match (
+                    option_env!(concat!($prefix, "_TARGET_HOST")),
+                    option_env!(concat!($prefix, "_TARGET_TRIPLE")),
+                    option_env!(concat!($prefix, "_TARGET_CFG_OS")),
+                    option_env!(concat!($prefix, "_TARGET_CFG_ARCH")),
+                    option_env!(concat!($prefix, "_TARGET_CFG_VENDOR")),
+                    option_env!(concat!($prefix, "_TARGET_CFG_ENDIAN")),
+                    option_env!(concat!($prefix, "_TARGET_CFG_POINTER_WIDTH")),
+                ) {
+                    (
+                        Some(host),
+                        Some(target),
+                        Some(os),
+                        Some(arch),
+                        Some(vendor),
+                        Some(endian),
+                        Some(pointer_width),
+                    ) => {
+                        use ::std::str::FromStr;
+
+                        let host = $crate::Host::from_str(host)?;
+                        let target = $crate::Host::from_str(target)?;
+                        let cfg = {
+                            let list = |list: Option<&'static str>| {
+                                let list = list.map_or_else(::std::vec::Vec::new, |list| {
+                                    list.split(',').map(::std::borrow::Cow::Borrowed).collect()
+                                });
+                                ::std::borrow::Cow::Owned(list)
+                            };
+
+                            let env = option_env!(concat!($prefix, "_TARGET_CFG_ENV"));
+                            let families = list(option_env!(concat!($prefix, "_TARGET_CFG_FAMILY")));
+                            let endian = $crate::Endian::from_str(endian)?;
+                            let pointer_width = pointer_width.parse()?;
+                            let atomics = list(option_env!(concat!($prefix, "_TARGET_CFG_HAS_ATOMIC")));
+                            let features = list(option_env!(concat!($prefix, "_TARGET_CFG_FEATURE")));
+                            let panic = match option_env!(concat!($prefix, "_TARGET_CFG_PANIC")) {
+                                Some(panic) => Some($crate::Panic::from_str(panic)?),
+                                None => None,
+                            };
+
+                            $crate::TargetCfg::new(
+                                ::std::borrow::Cow::Borrowed(os),
+                                ::std::borrow::Cow::Borrowed(arch),
+                                env.map(::std::borrow::Cow::Borrowed),
+                                ::std::borrow::Cow::Borrowed(vendor),
+                                families,
+                                endian,
+                                pointer_width,
+                                atomics,
+                                features,
+                                panic,
+                            )
+                        };
+
+                        Some($crate::Target::new(host, target, cfg))
+                    },
+                    _ => None,
+                }
+            }};
+        }
+
+        macro_rules! package {
+            () => {{
+                use ::std::str::FromStr;
+
+                let optional = |value: Option<&'static str>| {
+                    value
+                        .filter(|value| !value.is_empty())
+                        .map(::std::borrow::Cow::Borrowed)
+                };
 
-                    let channel = env!("CHKSUM_BUILD_INFO_RUST_CHANNEL");
-                    ::chksum_build::rust::Channel::from_str(channel)?
+                let name = env!("CARGO_PKG_NAME");
+                let version = $crate::PackageVersion::from_str(env!("CARGO_PKG_VERSION"))?;
+                let authors = option_env!("CARGO_PKG_AUTHORS").map_or_else(::std::vec::Vec::new, |authors| {
+                    authors
+                        .split(':')
+                        .filter(|author| !author.is_empty())
+                        .map(::std::borrow::Cow::Borrowed)
+                        .collect()
+                });
+                let authors = ::std::borrow::Cow::Owned(authors);
+                let description = optional(option_env!("CARGO_PKG_DESCRIPTION"));
+                let homepage = optional(option_env!("CARGO_PKG_HOMEPAGE"));
+                let repository = optional(option_env!("CARGO_PKG_REPOSITORY"));
+                let license = optional(option_env!("CARGO_PKG_LICENSE"));
+                let rust_version = match optional(option_env!("CARGO_PKG_RUST_VERSION")) {
+                    Some(rust_version) => Some($crate::ChannelVersion::from_str(&rust_version)?),
+                    None => None,
                 };
 
-                ::chksum_build::Rust::new(channel)
+                $crate::Package::new(
+                    ::std::borrow::Cow::Borrowed(name),
+                    version,
+                    authors,
+                    description,
+                    homepage,
+                    repository,
+                    license,
+                    rust_version,
+                )
+            }};
+        }
+
+        macro_rules! git {
+            () => {{
+                match option_env!(concat!($prefix, "_GIT_COMMIT_HASH")) {
+                    Some(commit_hash) => {
+                        let commit_short_hash =
+                            option_env!(concat!($prefix, "_GIT_COMMIT_SHORT_HASH")).unwrap_or(commit_hash);
+                        let commit_datetime = match option_env!(concat!($prefix, "_GIT_COMMIT_DATETIME")) {
+                            Some(commit_datetime) => {
+                                use $crate::__private::chrono::DateTime;
+
+                                Some(DateTime::parse_from_rfc3339(commit_datetime)?)
+                            },
+                            None => None,
+                        };
+                        let dirty = option_env!(concat!($prefix, "_GIT_DIRTY")) == Some("true");
+                        let branch = option_env!(concat!($prefix, "_GIT_BRANCH"));
+                        let tag = option_env!(concat!($prefix, "_GIT_TAG"));
+                        let path_in_vcs = option_env!(concat!($prefix, "_GIT_PATH_IN_VCS"));
+
+                        Some($crate::Git::new(
+                            ::std::borrow::Cow::Borrowed(commit_hash),
+                            ::std::borrow::Cow::Borrowed(commit_short_hash),
+                            commit_datetime,
+                            dirty,
+                            branch.map(::std::borrow::Cow::Borrowed),
+                            tag.map(::std::borrow::Cow::Borrowed),
+                            path_in_vcs.map(::std::borrow::Cow::Borrowed),
+                        ))
+                    },
+                    None => None,
+                }
             }};
         }
 
         let build = build!();
         let cargo = cargo!();
         let rust = rust!();
+        let target = target!();
+        let package = package!();
+        let git = git!();
 
-        ::chksum_build::BuildInfo::new(build, cargo, rust)
+        $crate::BuildInfo::new(build, cargo, rust, target, package, git)
     }};
 }
 
+/// Includes `BUILD_INFO` constant and `build_info` function generated by build script.
+///
+/// Unlike [`build_info`] macro values are parsed once by build script, so [`BuildInfo`] is available in const and
+/// static context without error handling. File is written to `OUT_DIR` directory unless disabled with
+/// `BuildScript::build_info_file`.
+///
+/// Included `build_info` function returns `&'static BuildInfo` backed by a single static, so macro can be used once
+/// at crate root and build information shared by all modules without passing it around.
+///
+/// File generated with custom prefix configured by `BuildScript::prefix` can be included with `prefix = "..."`
+/// argument.
+///
+/// Generated file refers to items by `::chksum_build` path, so `chksum-build` cannot be renamed in `dependencies`
+/// section of `Cargo.toml`.
+///
+/// # Examples
+///
+/// ```rust,ignore
+/// // main.rs or lib.rs
+/// chksum_build::include_build_info!();
+///
+/// mod cli {
+///     pub fn version() -> String {
+///         crate::build_info().package().version().to_string()
+///     }
+/// }
+/// ```
+#[macro_export]
+macro_rules! include_build_info {
+    () => {
+        include!(concat!(env!("OUT_DIR"), "/chksum_build_info.rs"));
+    };
+    (prefix = $prefix:literal $(,)?) => {
+        include!(concat!(env!("OUT_DIR"), "/chksum_build_info_", $prefix, ".rs"));
+    };
+}
+
 /// Contains informations about build.
 #[derive(Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
 pub struct Build {
-    datetime: NaiveDateTime,
+    datetime: DateTime<FixedOffset>,
+    datetime_source: DatetimeSource,
 }
 
 impl Build {
+    const LEGACY_DATETIME_FORMAT: &'static str = "%Y-%m-%d %H:%M:%S";
+
     #[cfg_attr(docsrs, doc(hidden))]
     #[inline]
     #[must_use]
-    pub const fn new(datetime: NaiveDateTime) -> Self {
-        Self { datetime }
+    pub const fn new(datetime: DateTime<FixedOffset>, datetime_source: DatetimeSource) -> Self {
+        Self {
+            datetime,
+            datetime_source,
+        }
+    }
+
+    /// Parses build datetime emitted by build script.
+    ///
+    /// Accepts [RFC 3339](https://www.rfc-editor.org/rfc/rfc3339) datetime as well as `%Y-%m-%d %H:%M:%S` format
+    /// emitted by previous versions which is treated as UTC.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error when datetime is in neither of formats.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use chksum_build::Build;
+    ///
+    /// let datetime = Build::parse_datetime("2023-01-02T10:20:30+01:00")?;
+    /// assert_eq!(datetime, Build::parse_datetime("2023-01-02 09:20:30")?);
+    /// # Ok::<(), chrono::ParseError>(())
+    /// ```
+    pub fn parse_datetime(datetime: &str) -> Result<DateTime<FixedOffset>, ParseError> {
+        DateTime::parse_from_rfc3339(datetime).or_else(|error| {
+            NaiveDateTime::parse_from_str(datetime, Self::LEGACY_DATETIME_FORMAT)
+                .map(|datetime| Utc.fix().from_utc_datetime(&datetime))
+                .map_err(|_| error)
+        })
     }
 
     /// Returns build datetime.
     #[inline]
     #[must_use]
-    pub const fn datetime(&self) -> &NaiveDateTime {
+    pub const fn datetime(&self) -> &DateTime<FixedOffset> {
         &self.datetime
     }
+
+    /// Returns build datetime in UTC.
+    #[inline]
+    #[must_use]
+    pub fn datetime_utc(&self) -> DateTime<Utc> {
+        self.datetime.with_timezone(&Utc)
+    }
+
+    /// Returns source of build datetime.
+    ///
+    /// Check [`DatetimeSource`] for more details.
+    #[inline]
+    #[must_use]
+    pub const fn datetime_source(&self) -> &DatetimeSource {
+        &self.datetime_source
+    }
 }
 
 /// Contains informations about Cargo.
 #[derive(Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
 pub struct Cargo {
     profile: Profile,
+    opt_level: Cow<'static, str>,
+    debug: bool,
+    debug_assertions: bool,
+    features: Cow<'static, [Cow<'static, str>]>,
 }
 
 impl Cargo {
     #[cfg_attr(docsrs, doc(hidden))]
     #[inline]
     #[must_use]
-    pub const fn new(profile: Profile) -> Self {
-        Self { profile }
+    pub const fn new(
+        profile: Profile,
+        opt_level: Cow<'static, str>,
+        debug: bool,
+        debug_assertions: bool,
+        features: Cow<'static, [Cow<'static, str>]>,
+    ) -> Self {
+        Self {
+            profile,
+            opt_level,
+            debug,
+            debug_assertions,
+            features,
+        }
     }
 
     /// Returns Cargo profile.
@@ -114,20 +416,93 @@ impl Cargo {
     pub const fn profile(&self) -> &Profile {
         &self.profile
     }
+
+    /// Returns optimization level of profile, e.g. `3` or `s`.
+    #[inline]
+    #[must_use]
+    pub fn opt_level(&self) -> &str {
+        &self.opt_level
+    }
+
+    /// Returns `true` when debug information was enabled by profile.
+    #[inline]
+    #[must_use]
+    pub const fn debug(&self) -> bool {
+        self.debug
+    }
+
+    /// Returns `true` when debug assertions were enabled.
+    #[inline]
+    #[must_use]
+    pub const fn debug_assertions(&self) -> bool {
+        self.debug_assertions
+    }
+
+    /// Returns sorted names of enabled features as spelled in `Cargo.toml`.
+    #[inline]
+    pub fn features(&self) -> impl Iterator<Item = &str> {
+        self.features.iter().map(AsRef::as_ref)
+    }
+
+    /// Returns `true` when given feature was enabled.
+    #[inline]
+    #[must_use]
+    pub fn has_feature(&self, feature: &str) -> bool {
+        self.features().any(|item| item == feature)
+    }
 }
 
 /// Contains informations about Rust.
 #[derive(Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
 pub struct Rust {
     channel: Channel,
+    version: Option<ChannelVersion>,
+    commit_hash: Option<Cow<'static, str>>,
+    commit_date: Option<NaiveDate>,
+    llvm_version: Option<Cow<'static, str>>,
 }
 
 impl Rust {
+    const COMMIT_DATE_FORMAT: &'static str = "%Y-%m-%d";
+
     #[cfg_attr(docsrs, doc(hidden))]
     #[inline]
     #[must_use]
-    pub const fn new(channel: Channel) -> Self {
-        Self { channel }
+    pub const fn new(
+        channel: Channel,
+        version: Option<ChannelVersion>,
+        commit_hash: Option<Cow<'static, str>>,
+        commit_date: Option<NaiveDate>,
+        llvm_version: Option<Cow<'static, str>>,
+    ) -> Self {
+        Self {
+            channel,
+            version,
+            commit_hash,
+            commit_date,
+            llvm_version,
+        }
+    }
+
+    /// Parses compiler commit date emitted by build script.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error when date isn't in `%Y-%m-%d` format.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use chksum_build::Rust;
+    /// use chrono::NaiveDate;
+    ///
+    /// let date = Rust::parse_commit_date("2023-12-04")?;
+    /// assert_eq!(Some(date), NaiveDate::from_ymd_opt(2023, 12, 4));
+    /// # Ok::<(), chrono::ParseError>(())
+    /// ```
+    pub fn parse_commit_date(date: &str) -> Result<NaiveDate, ParseError> {
+        NaiveDate::parse_from_str(date, Self::COMMIT_DATE_FORMAT)
     }
 
     /// Returns Rust channel.
@@ -138,43 +513,552 @@ impl Rust {
     pub const fn channel(&self) -> &Channel {
         &self.channel
     }
+
+    /// Returns compiler version, e.g. `1.74.1`.
+    ///
+    /// Check [`ChannelVersion`] for more details.
+    #[inline]
+    #[must_use]
+    pub const fn version(&self) -> Option<&ChannelVersion> {
+        self.version.as_ref()
+    }
+
+    /// Returns commit hash of compiler.
+    #[inline]
+    #[must_use]
+    pub fn commit_hash(&self) -> Option<&str> {
+        self.commit_hash.as_deref()
+    }
+
+    /// Returns commit date of compiler.
+    #[inline]
+    #[must_use]
+    pub const fn commit_date(&self) -> Option<&NaiveDate> {
+        self.commit_date.as_ref()
+    }
+
+    /// Returns LLVM version of compiler.
+    #[inline]
+    #[must_use]
+    pub fn llvm_version(&self) -> Option<&str> {
+        self.llvm_version.as_deref()
+    }
+}
+
+/// Contains informations about target.
+#[derive(Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub struct Target {
+    host: Host,
+    target: Host,
+    cfg: TargetCfg,
+}
+
+impl Target {
+    #[cfg_attr(docsrs, doc(hidden))]
+    #[inline]
+    #[must_use]
+    pub const fn new(host: Host, target: Host, cfg: TargetCfg) -> Self {
+        Self { host, target, cfg }
+    }
+
+    /// Returns triple of machine which compiled crate.
+    ///
+    /// Check [`Host`] for more details.
+    #[inline]
+    #[must_use]
+    pub const fn host(&self) -> &Host {
+        &self.host
+    }
+
+    /// Returns triple of machine which crate was compiled for.
+    ///
+    /// Check [`Host`] for more details.
+    #[inline]
+    #[must_use]
+    pub const fn target(&self) -> &Host {
+        &self.target
+    }
+
+    /// Returns `true` when host and target triples differ.
+    #[inline]
+    #[must_use]
+    pub fn is_cross_compiled(&self) -> bool {
+        self.host != self.target
+    }
+
+    /// Returns configuration of target.
+    ///
+    /// Check [`TargetCfg`] for more details.
+    #[inline]
+    #[must_use]
+    pub const fn cfg(&self) -> &TargetCfg {
+        &self.cfg
+    }
+}
+
+/// Contains informations about target configuration set by Cargo in `CARGO_CFG_TARGET_*` variables.
+///
+/// Resources:
+/// * [The Rust Reference: Set configuration options](https://doc.rust-lang.org/reference/conditional-compilation.html#set-configuration-options).
+#[derive(Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub struct TargetCfg {
+    os: Cow<'static, str>,
+    arch: Cow<'static, str>,
+    env: Option<Cow<'static, str>>,
+    vendor: Cow<'static, str>,
+    families: Cow<'static, [Cow<'static, str>]>,
+    endian: Endian,
+    pointer_width: u32,
+    atomics: Cow<'static, [Cow<'static, str>]>,
+    features: Cow<'static, [Cow<'static, str>]>,
+    panic: Option<Panic>,
+}
+
+impl TargetCfg {
+    #[allow(clippy::too_many_arguments)]
+    #[cfg_attr(docsrs, doc(hidden))]
+    #[inline]
+    #[must_use]
+    pub const fn new(
+        os: Cow<'static, str>,
+        arch: Cow<'static, str>,
+        env: Option<Cow<'static, str>>,
+        vendor: Cow<'static, str>,
+        families: Cow<'static, [Cow<'static, str>]>,
+        endian: Endian,
+        pointer_width: u32,
+        atomics: Cow<'static, [Cow<'static, str>]>,
+        features: Cow<'static, [Cow<'static, str>]>,
+        panic: Option<Panic>,
+    ) -> Self {
+        Self {
+            os,
+            arch,
+            env,
+            vendor,
+            families,
+            endian,
+            pointer_width,
+            atomics,
+            features,
+            panic,
+        }
+    }
+
+    /// Returns operating system, e.g. `linux`.
+    #[inline]
+    #[must_use]
+    pub fn os(&self) -> &str {
+        &self.os
+    }
+
+    /// Returns architecture, e.g. `x86_64`.
+    #[inline]
+    #[must_use]
+    pub fn arch(&self) -> &str {
+        &self.arch
+    }
+
+    /// Returns ABI or C library, e.g. `gnu`.
+    ///
+    /// Returns `None` when target doesn't specify it.
+    #[inline]
+    #[must_use]
+    pub fn env(&self) -> Option<&str> {
+        self.env.as_deref()
+    }
+
+    /// Returns vendor, e.g. `unknown`.
+    #[inline]
+    #[must_use]
+    pub fn vendor(&self) -> &str {
+        &self.vendor
+    }
+
+    /// Returns families, e.g. `unix`.
+    #[inline]
+    pub fn families(&self) -> impl Iterator<Item = &str> {
+        self.families.iter().map(AsRef::as_ref)
+    }
+
+    /// Returns `true` when target belongs to given family.
+    #[inline]
+    #[must_use]
+    pub fn has_family(&self, family: &str) -> bool {
+        self.families().any(|item| item == family)
+    }
+
+    /// Returns endianness.
+    ///
+    /// Check [`Endian`] for more details.
+    #[inline]
+    #[must_use]
+    pub const fn endian(&self) -> &Endian {
+        &self.endian
+    }
+
+    /// Returns pointer width in bits.
+    #[inline]
+    #[must_use]
+    pub const fn pointer_width(&self) -> u32 {
+        self.pointer_width
+    }
+
+    /// Returns widths of supported atomic types, e.g. `64` or `ptr`.
+    #[inline]
+    pub fn atomics(&self) -> impl Iterator<Item = &str> {
+        self.atomics.iter().map(AsRef::as_ref)
+    }
+
+    /// Returns `true` when atomic types of given width are supported.
+    #[inline]
+    #[must_use]
+    pub fn has_atomic(&self, width: &str) -> bool {
+        self.atomics().any(|item| item == width)
+    }
+
+    /// Returns enabled target features, e.g. `sse2`.
+    #[inline]
+    pub fn features(&self) -> impl Iterator<Item = &str> {
+        self.features.iter().map(AsRef::as_ref)
+    }
+
+    /// Returns `true` when given target feature is enabled.
+    #[inline]
+    #[must_use]
+    pub fn has_feature(&self, feature: &str) -> bool {
+        self.features().any(|item| item == feature)
+    }
+
+    /// Returns panic strategy.
+    ///
+    /// Returns `None` when Cargo didn't provide it.
+    ///
+    /// Check [`Panic`] for more details.
+    #[inline]
+    #[must_use]
+    pub const fn panic(&self) -> Option<&Panic> {
+        self.panic.as_ref()
+    }
+}
+
+/// Contains informations about package.
+///
+/// Read from `CARGO_PKG_*` variables set by Cargo, empty values are treated as missing.
+#[derive(Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub struct Package {
+    name: Cow<'static, str>,
+    version: PackageVersion,
+    authors: Cow<'static, [Cow<'static, str>]>,
+    description: Option<Cow<'static, str>>,
+    homepage: Option<Cow<'static, str>>,
+    repository: Option<Cow<'static, str>>,
+    license: Option<Cow<'static, str>>,
+    rust_version: Option<ChannelVersion>,
+}
+
+impl Package {
+    #[allow(clippy::too_many_arguments)]
+    #[cfg_attr(docsrs, doc(hidden))]
+    #[inline]
+    #[must_use]
+    pub const fn new(
+        name: Cow<'static, str>,
+        version: PackageVersion,
+        authors: Cow<'static, [Cow<'static, str>]>,
+        description: Option<Cow<'static, str>>,
+        homepage: Option<Cow<'static, str>>,
+        repository: Option<Cow<'static, str>>,
+        license: Option<Cow<'static, str>>,
+        rust_version: Option<ChannelVersion>,
+    ) -> Self {
+        Self {
+            name,
+            version,
+            authors,
+            description,
+            homepage,
+            repository,
+            license,
+            rust_version,
+        }
+    }
+
+    /// Returns package name.
+    #[inline]
+    #[must_use]
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Returns package version.
+    ///
+    /// Check [`PackageVersion`] for more details.
+    #[inline]
+    #[must_use]
+    pub const fn version(&self) -> &PackageVersion {
+        &self.version
+    }
+
+    /// Returns package authors.
+    #[inline]
+    pub fn authors(&self) -> impl Iterator<Item = &str> {
+        self.authors.iter().map(AsRef::as_ref)
+    }
+
+    /// Returns package description.
+    #[inline]
+    #[must_use]
+    pub fn description(&self) -> Option<&str> {
+        self.description.as_deref()
+    }
+
+    /// Returns package homepage.
+    #[inline]
+    #[must_use]
+    pub fn homepage(&self) -> Option<&str> {
+        self.homepage.as_deref()
+    }
+
+    /// Returns package repository.
+    #[inline]
+    #[must_use]
+    pub fn repository(&self) -> Option<&str> {
+        self.repository.as_deref()
+    }
+
+    /// Returns package license expression.
+    #[inline]
+    #[must_use]
+    pub fn license(&self) -> Option<&str> {
+        self.license.as_deref()
+    }
+
+    /// Returns minimum supported Rust version of package.
+    ///
+    /// Check [`ChannelVersion`] for more details.
+    #[inline]
+    #[must_use]
+    pub const fn rust_version(&self) -> Option<&ChannelVersion> {
+        self.rust_version.as_ref()
+    }
+}
+
+/// Contains informations about Git.
+///
+/// Available only when crate is built from a Git repository or from a packaged crate with `.cargo_vcs_info.json`
+/// file.
+#[derive(Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub struct Git {
+    commit_hash: Cow<'static, str>,
+    commit_short_hash: Cow<'static, str>,
+    commit_datetime: Option<DateTime<FixedOffset>>,
+    #[cfg_attr(feature = "serde", serde(rename = "is_dirty"))]
+    dirty: bool,
+    branch: Option<Cow<'static, str>>,
+    tag: Option<Cow<'static, str>>,
+    path_in_vcs: Option<Cow<'static, str>>,
+}
+
+impl Git {
+    #[cfg_attr(docsrs, doc(hidden))]
+    #[inline]
+    #[must_use]
+    pub const fn new(
+        commit_hash: Cow<'static, str>,
+        commit_short_hash: Cow<'static, str>,
+        commit_datetime: Option<DateTime<FixedOffset>>,
+        dirty: bool,
+        branch: Option<Cow<'static, str>>,
+        tag: Option<Cow<'static, str>>,
+        path_in_vcs: Option<Cow<'static, str>>,
+    ) -> Self {
+        Self {
+            commit_hash,
+            commit_short_hash,
+            commit_datetime,
+            dirty,
+            branch,
+            tag,
+            path_in_vcs,
+        }
+    }
+
+    /// Returns full hash of `HEAD` commit.
+    #[inline]
+    #[must_use]
+    pub fn commit_hash(&self) -> &str {
+        &self.commit_hash
+    }
+
+    /// Returns abbreviated hash of `HEAD` commit.
+    #[inline]
+    #[must_use]
+    pub fn commit_short_hash(&self) -> &str {
+        &self.commit_short_hash
+    }
+
+    /// Returns committer datetime of `HEAD` commit.
+    ///
+    /// Returns `None` when crate was built from a packaged crate.
+    #[inline]
+    #[must_use]
+    pub const fn commit_datetime(&self) -> Option<&DateTime<FixedOffset>> {
+        self.commit_datetime.as_ref()
+    }
+
+    /// Returns `true` when working tree contained uncommitted changes.
+    #[inline]
+    #[must_use]
+    pub const fn is_dirty(&self) -> bool {
+        self.dirty
+    }
+
+    /// Returns checked out branch or `None` when `HEAD` was detached.
+    #[inline]
+    #[must_use]
+    pub fn branch(&self) -> Option<&str> {
+        self.branch.as_deref()
+    }
+
+    /// Returns the latest tag reachable from `HEAD`.
+    #[inline]
+    #[must_use]
+    pub fn tag(&self) -> Option<&str> {
+        self.tag.as_deref()
+    }
+
+    /// Returns crate's path relative to repository root.
+    ///
+    /// Available only when crate was built from a packaged crate.
+    #[inline]
+    #[must_use]
+    pub fn path_in_vcs(&self) -> Option<&str> {
+        self.path_in_vcs.as_deref()
+    }
 }
 
 /// Contains values set by build script.
 #[allow(clippy::module_name_repetitions)]
 #[derive(Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
 pub struct BuildInfo {
-    build: Build,
-    cargo: Cargo,
-    rust: Rust,
+    build: Option<Build>,
+    cargo: Option<Cargo>,
+    rust: Option<Rust>,
+    target: Option<Target>,
+    package: Package,
+    git: Option<Git>,
 }
 
 impl BuildInfo {
     #[cfg_attr(docsrs, doc(hidden))]
     #[inline]
     #[must_use]
-    pub const fn new(build: Build, cargo: Cargo, rust: Rust) -> Self {
-        Self { build, cargo, rust }
+    pub const fn new(
+        build: Option<Build>,
+        cargo: Option<Cargo>,
+        rust: Option<Rust>,
+        target: Option<Target>,
+        package: Package,
+        git: Option<Git>,
+    ) -> Self {
+        Self {
+            build,
+            cargo,
+            rust,
+            target,
+            package,
+            git,
+        }
     }
 
     /// Returns informations about build.
+    ///
+    /// Returns `None` when `build` section is disabled in build script.
     #[inline]
     #[must_use]
-    pub const fn build(&self) -> &Build {
-        &self.build
+    pub const fn build(&self) -> Option<&Build> {
+        self.build.as_ref()
     }
 
     /// Returns informations about Cargo.
+    ///
+    /// Returns `None` when `cargo` section is disabled in build script.
     #[inline]
     #[must_use]
-    pub const fn cargo(&self) -> &Cargo {
-        &self.cargo
+    pub const fn cargo(&self) -> Option<&Cargo> {
+        self.cargo.as_ref()
     }
 
     /// Returns informations about Rust.
+    ///
+    /// Returns `None` when `rust` section is disabled in build script.
     #[inline]
     #[must_use]
-    pub const fn rust(&self) -> &Rust {
-        &self.rust
+    pub const fn rust(&self) -> Option<&Rust> {
+        self.rust.as_ref()
     }
+
+    /// Returns informations about target.
+    ///
+    /// Returns `None` when `target` section is disabled in build script.
+    #[inline]
+    #[must_use]
+    pub const fn target(&self) -> Option<&Target> {
+        self.target.as_ref()
+    }
+
+    /// Returns informations about package.
+    #[inline]
+    #[must_use]
+    pub const fn package(&self) -> &Package {
+        &self.package
+    }
+
+    /// Returns informations about Git.
+    ///
+    /// Returns `None` when crate wasn't built from a Git repository.
+    #[inline]
+    #[must_use]
+    pub const fn git(&self) -> Option<&Git> {
+        self.git.as_ref()
+    }
+}
+
+/// Creates date in const context, used by file generated by build script.
+#[must_use]
+pub const fn date(year: i32, month: u32, day: u32) -> NaiveDate {
+    match NaiveDate::from_ymd_opt(year, month, day) {
+        Some(date) => date,
+        None => panic!("invalid date"),
+    }
+}
+
+/// Creates datetime from UTC values and offset in seconds in const context, used by file generated by build script.
+#[allow(clippy::too_many_arguments)]
+#[must_use]
+pub const fn datetime(
+    year: i32,
+    month: u32,
+    day: u32,
+    hour: u32,
+    minute: u32,
+    second: u32,
+    nanosecond: u32,
+    offset: i32,
+) -> DateTime<FixedOffset> {
+    let time = match NaiveTime::from_hms_nano_opt(hour, minute, second, nanosecond) {
+        Some(time) => time,
+        None => panic!("invalid time"),
+    };
+    let offset = match FixedOffset::east_opt(offset) {
+        Some(offset) => offset,
+        None => panic!("invalid offset"),
+    };
+    DateTime::from_naive_utc_and_offset(NaiveDateTime::new(date(year, month, day), time), offset)
 }
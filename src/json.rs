@@ -0,0 +1,199 @@
+//! Minimal JSON parser.
+//!
+//! Used to read small files generated by Cargo, e.g. `.cargo_vcs_info.json`.
+//!
+//! Resources:
+//! * [RFC 8259: The JavaScript Object Notation (JSON) Data Interchange Format](https://www.rfc-editor.org/rfc/rfc8259).
+
+use std::collections::BTreeMap;
+use std::result;
+use std::str::FromStr;
+
+use nom::branch::alt;
+use nom::bytes::complete::{escaped_transform, is_not, tag, take_while_m_n};
+use nom::character::complete::{char, multispace0};
+use nom::combinator::{all_consuming, map, map_opt, map_res, value};
+use nom::error::{context, VerboseError};
+use nom::multi::separated_list0;
+use nom::number::complete::double;
+use nom::sequence::{delimited, preceded, separated_pair};
+use nom::{Finish, IResult};
+
+use crate::error::Error;
+
+/// A JSON value.
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) enum Value {
+    Null,
+    Bool(bool),
+    Number(f64),
+    String(String),
+    Array(Vec<Value>),
+    Object(BTreeMap<String, Value>),
+}
+
+impl Value {
+    /// Returns value of object member.
+    pub(crate) fn get(&self, key: &str) -> Option<&Self> {
+        match self {
+            Self::Object(members) => members.get(key),
+            _ => None,
+        }
+    }
+
+    /// Returns string value.
+    pub(crate) fn as_str(&self) -> Option<&str> {
+        match self {
+            Self::String(string) => Some(string),
+            _ => None,
+        }
+    }
+
+    /// Returns boolean value.
+    pub(crate) const fn as_bool(&self) -> Option<bool> {
+        match self {
+            Self::Bool(bool) => Some(*bool),
+            _ => None,
+        }
+    }
+
+    /// Parse value surrounded by optional whitespaces.
+    fn nom_parse(input: &str) -> IResult<&str, Self, VerboseError<&str>> {
+        let null = value(Self::Null, tag("null"));
+        let bool = alt((
+            value(Self::Bool(true), tag("true")),
+            value(Self::Bool(false), tag("false")),
+        ));
+        let number = map(double, Self::Number);
+        let string = map(Self::nom_parse_string, Self::String);
+        let array = map(Self::nom_parse_array, Self::Array);
+        let object = map(Self::nom_parse_object, Self::Object);
+
+        let parser = alt((null, bool, string, array, object, number));
+
+        context("value", delimited(multispace0, parser, multispace0))(input)
+    }
+
+    /// Parse string.
+    fn nom_parse_string(input: &str) -> IResult<&str, String, VerboseError<&str>> {
+        let unicode = map_opt(
+            preceded(
+                char('u'),
+                map_res(take_while_m_n(4, 4, |char: char| char.is_ascii_hexdigit()), |hex| {
+                    u32::from_str_radix(hex, 16)
+                }),
+            ),
+            char::from_u32,
+        );
+        let escape = alt((
+            value('"', char('"')),
+            value('\\', char('\\')),
+            value('/', char('/')),
+            value('\u{8}', char('b')),
+            value('\u{C}', char('f')),
+            value('\n', char('n')),
+            value('\r', char('r')),
+            value('\t', char('t')),
+            unicode,
+        ));
+        let characters = escaped_transform(is_not("\"\\"), '\\', escape);
+        let empty = map(tag(""), |_| String::new());
+
+        let parser = delimited(char('"'), alt((characters, empty)), char('"'));
+
+        context("string", parser)(input)
+    }
+
+    /// Parse array.
+    fn nom_parse_array(input: &str) -> IResult<&str, Vec<Self>, VerboseError<&str>> {
+        let elements = separated_list0(char(','), Self::nom_parse);
+
+        let parser = delimited(char('['), delimited(multispace0, elements, multispace0), char(']'));
+
+        context("array", parser)(input)
+    }
+
+    /// Parse object.
+    fn nom_parse_object(input: &str) -> IResult<&str, BTreeMap<String, Self>, VerboseError<&str>> {
+        let key = delimited(multispace0, Self::nom_parse_string, multispace0);
+        let member = separated_pair(key, char(':'), Self::nom_parse);
+        let members = map(separated_list0(char(','), member), |members| {
+            members.into_iter().collect()
+        });
+
+        let parser = delimited(char('{'), delimited(multispace0, members, multispace0), char('}'));
+
+        context("object", parser)(input)
+    }
+}
+
+impl FromStr for Value {
+    type Err = Error;
+
+    fn from_str(s: &str) -> result::Result<Self, Self::Err> {
+        let mut parser = context("json", all_consuming(Self::nom_parse));
+
+        let (_, value) = parser(s).finish().map_err(|error| {
+            let errors = error
+                .errors
+                .into_iter()
+                .map(|(input, kind)| (input.to_string(), kind))
+                .collect();
+            let error = VerboseError { errors };
+            Error::Nom(error)
+        })?;
+
+        Ok(value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use anyhow::Result;
+
+    use super::*;
+
+    #[test]
+    fn test_value_from_str() -> Result<()> {
+        assert_eq!(Value::from_str("null")?, Value::Null);
+        assert_eq!(Value::from_str(" true ")?, Value::Bool(true));
+        assert_eq!(Value::from_str("false")?, Value::Bool(false));
+        assert_eq!(Value::from_str("-1.5e2")?, Value::Number(-150.0));
+        assert_eq!(Value::from_str(r#""""#)?, Value::String(String::new()));
+        assert_eq!(
+            Value::from_str(r#""a\"b\\c\/d\n\u0041""#)?,
+            Value::String(String::from("a\"b\\c/d\nA"))
+        );
+        assert_eq!(Value::from_str("[ ]")?, Value::Array(Vec::new()));
+        assert_eq!(
+            Value::from_str("[1, \"2\", [null]]")?,
+            Value::Array(vec![
+                Value::Number(1.0),
+                Value::String(String::from("2")),
+                Value::Array(vec![Value::Null]),
+            ])
+        );
+        let object = Value::from_str("{\n  \"git\": {\n    \"sha1\": \"abc\",\n    \"dirty\": true\n  }\n}\n")?;
+        assert_eq!(
+            object
+                .get("git")
+                .and_then(|git| git.get("sha1"))
+                .and_then(Value::as_str),
+            Some("abc")
+        );
+        assert_eq!(
+            object
+                .get("git")
+                .and_then(|git| git.get("dirty"))
+                .and_then(Value::as_bool),
+            Some(true)
+        );
+        assert_eq!(object.get("path_in_vcs"), None);
+        assert!(Value::from_str("").is_err());
+        assert!(Value::from_str("{\"a\" 1}").is_err());
+        assert!(Value::from_str("[1,]").is_err());
+        assert!(Value::from_str("\"unterminated").is_err());
+        assert!(Value::from_str("nul").is_err());
+        Ok(())
+    }
+}
@@ -83,6 +83,30 @@
 //! # }
 //! ```
 //!
+//! ## `include_build_info` macro
+//!
+//! [`include_build_info`] macro includes `BUILD_INFO` constant generated by build script, so [`BuildInfo`] can be
+//! used in const or static context.
+//!
+//! ```rust,ignore
+//! use chksum_build::{include_build_info, BuildInfo};
+//!
+//! include_build_info!();
+//!
+//! static BUILD_INFO_REF: &BuildInfo = &BUILD_INFO;
+//! ```
+//!
+//! It also includes `build_info` function which returns a single `&'static BuildInfo` instance shared by whole crate.
+//!
+//! ```rust,ignore
+//! chksum_build::include_build_info!();
+//!
+//! fn log_version() {
+//!     let build_info = crate::build_info();
+//!     println!("{} {}", build_info.package().name(), build_info.package().version());
+//! }
+//! ```
+//!
 //! ## `env` or `option_env` macros
 //!
 //! [`env`] or [`option_env`] macros.
@@ -91,7 +115,7 @@
 //!
 //! ```rust,ignore
 //! use std::str::FromStr;
-//! use chksum_build::cargo::Profile;
+//! use chksum_build::Profile;
 //!
 //! // ...
 //!
@@ -114,15 +138,20 @@
 //! Check [`Profile`] for more details.
 //!
 //! ```rust
-//! #[cfg(debug)]
+//! #[cfg(chksum_profile = "debug")]
 //! fn debug_function() {
 //!     // ...
 //! }
 //!
-//! #[cfg_attr(release, inline)]
+//! #[cfg_attr(chksum_profile = "release", inline)]
 //! fn inline_when_release_function() {
 //!     // ...
 //! }
+//!
+//! #[cfg(chksum_profile = "dist")]
+//! fn custom_profile_function() {
+//!     // ...
+//! }
 //! ```
 //!
 //! ### `Channel` variants
@@ -130,28 +159,133 @@
 //! Check [`Channel`] for more details.
 //!
 //! ```rust
-//! #[cfg(stable)]
+//! #[cfg(chksum_channel = "stable")]
 //! fn stable_function() {
 //!     // ...
 //! }
 //!
-//! #[cfg(nightly)]
+//! #[cfg(chksum_channel = "nightly")]
 //! fn nightly_function() {
 //!     // ...
 //! }
 //!
-//! #[cfg_attr(nightly, optimize(size))]
+//! #[cfg_attr(chksum_channel = "nightly", optimize(size))]
 //! fn optimize_when_nightly_function() {
 //!     // ...
 //! }
 //! ```
 //!
+//! ### Compiler version
+//!
+//! A `chksum_rust_1_N` option is enabled for every minor version of compiler up to detected one. Check
+//! [`BuildScript::rust_version_cfg`] for more details.
+//!
+//! ```rust
+//! #[cfg(chksum_rust_1_70)]
+//! fn function_using_is_some_and() {
+//!     // ...
+//! }
+//! ```
+//!
+//! ### Target triples
+//!
+//! `chksum_host` and `chksum_target` options contain triples of machine which compiles crate and machine which crate
+//! is compiled for, `chksum_cross_compiled` option is enabled when they differ.
+//!
+//! ```rust
+//! #[cfg(chksum_cross_compiled)]
+//! fn cross_compiled_function() {
+//!     // ...
+//! }
+//!
+//! #[cfg(chksum_host = "x86_64-unknown-linux-gnu")]
+//! fn built_on_linux_function() {
+//!     // ...
+//! }
+//! ```
+//!
+//! ### Bare options
+//!
+//! Bare configuration options like `release` or `nightly` emitted by previous versions can be enabled with
+//! [`BuildScript::bare_cfg`].
+//!
+//! ```rust
+//! #[cfg(release)]
+//! fn release_function() {
+//!     // ...
+//! }
+//! ```
+//!
 //! # Feature flags
 //!
 //! * `info`: Enables items required by library or application.
 //! * `script`: Enables items required by build script.
-//!
-//! By default both of them are enabled.
+//! * `serde`: Implements [`Serialize`](https://docs.rs/serde/latest/serde/trait.Serialize.html) and
+//!   [`Deserialize`](https://docs.rs/serde/latest/serde/trait.Deserialize.html) for [`BuildInfo`] and its
+//!   components.
+//!
+//! By default `info` and `script` features are enabled.
+//!
+//! ## Serialization
+//!
+//! Structures are serialized as maps with fields named like their methods, enums, versions and target triples as
+//! their `Display` strings and datetimes in RFC 3339 format. Missing optional values and disabled sections are
+//! serialized as `null`.
+//!
+//! ```json
+//! {
+//!   "build": { "datetime": "2023-01-02T10:20:30+01:00", "datetime_source": "fixed" },
+//!   "cargo": {
+//!     "profile": "dist:release",
+//!     "opt_level": "3",
+//!     "debug": false,
+//!     "debug_assertions": false,
+//!     "features": ["default"]
+//!   },
+//!   "rust": {
+//!     "channel": "stable",
+//!     "version": "1.74.1",
+//!     "commit_hash": "a28077b28a02b92985b3a3faecf92813155f1ea1",
+//!     "commit_date": "2023-12-04",
+//!     "llvm_version": "17.0.4"
+//!   },
+//!   "target": {
+//!     "host": "x86_64-unknown-linux-gnu",
+//!     "target": "x86_64-unknown-linux-gnu",
+//!     "cfg": {
+//!       "os": "linux",
+//!       "arch": "x86_64",
+//!       "env": "gnu",
+//!       "vendor": "unknown",
+//!       "families": ["unix"],
+//!       "endian": "little",
+//!       "pointer_width": 64,
+//!       "atomics": ["16", "32", "64", "8", "ptr"],
+//!       "features": ["fxsr", "sse", "sse2"],
+//!       "panic": "unwind"
+//!     }
+//!   },
+//!   "package": {
+//!     "name": "crate",
+//!     "version": "0.1.0",
+//!     "authors": [],
+//!     "description": null,
+//!     "homepage": null,
+//!     "repository": null,
+//!     "license": "MIT",
+//!     "rust_version": "1.61"
+//!   },
+//!   "git": {
+//!     "commit_hash": "0123456789abcdef0123456789abcdef01234567",
+//!     "commit_short_hash": "0123456",
+//!     "commit_datetime": "2023-01-02T10:20:30+01:00",
+//!     "is_dirty": false,
+//!     "branch": "main",
+//!     "tag": "v0.1.0",
+//!     "path_in_vcs": null
+//!   }
+//! }
+//! ```
 //!
 //! # Alternatives
 //!
@@ -169,24 +303,67 @@
 #![cfg_attr(tarpaulin, feature(no_coverage))]
 #![forbid(unsafe_code)]
 
+#[cfg_attr(docsrs, doc(hidden))]
+mod build;
 #[cfg_attr(docsrs, doc(hidden))]
 mod cargo;
+#[cfg(feature = "script")]
+mod codegen;
 #[cfg_attr(docsrs, doc(hidden))]
 pub mod error;
+#[cfg(feature = "script")]
+mod git;
 #[cfg(feature = "info")]
 #[cfg_attr(docsrs, doc(cfg(feature = "info")))]
 #[cfg_attr(tarpaulin, no_coverage)]
 mod info;
+#[cfg(feature = "script")]
+mod json;
+#[cfg(feature = "script")]
+mod manifest;
+#[cfg_attr(docsrs, doc(hidden))]
+mod package;
 #[cfg_attr(docsrs, doc(hidden))]
 mod rust;
 #[cfg(feature = "script")]
 #[cfg_attr(docsrs, doc(cfg(feature = "script")))]
 mod script;
+#[cfg(feature = "serde")]
+mod serialization;
+#[cfg_attr(docsrs, doc(hidden))]
+mod target;
 
-pub use cargo::Profile;
+pub use build::DatetimeSource;
+pub use cargo::{BaseProfile, Profile};
 pub use error::{Error, Result};
 #[cfg(feature = "info")]
-pub use info::{Build, BuildInfo, Cargo, Rust};
-pub use rust::{Channel, ChannelVersion};
+pub use info::{Build, BuildInfo, Cargo, Git, Package, Rust, Target, TargetCfg};
+pub use package::PackageVersion;
+pub use rust::{
+    Abi,
+    Architecture,
+    Channel,
+    ChannelVersion,
+    Host,
+    LinuxAbi,
+    RustcVersion,
+    System,
+    Toolchain,
+    Vendor,
+    WindowsAbi,
+};
 #[cfg(feature = "script")]
-pub use script::{setup, BuildScript};
+pub use script::{setup, BuildScript, Mode};
+pub use target::{Endian, Panic};
+
+#[doc(hidden)]
+pub mod __private {
+    //! Items used by macros, generated code and `chksum-build-macros` crate, not a part of public API.
+
+    pub use chrono;
+
+    #[cfg(feature = "script")]
+    pub use crate::codegen::build_info_expr;
+    #[cfg(feature = "info")]
+    pub use crate::info::{date, datetime};
+}
@@ -0,0 +1,163 @@
+//! Minimal Cargo manifest reader.
+//!
+//! Only names of features, explicit and implicit ones defined by optional dependencies, are read. Manifest is
+//! scanned line by line, values other than inline tables of dependencies aren't parsed.
+//!
+//! Resources:
+//! * [The Cargo Book: Features](https://doc.rust-lang.org/cargo/reference/features.html),
+//! * [TOML v1.0.0](https://toml.io/en/v1.0.0).
+
+use nom::branch::alt;
+use nom::bytes::complete::{is_not, take_while1};
+use nom::character::complete::{char, space0};
+use nom::combinator::map;
+use nom::error::{context, VerboseError};
+use nom::multi::separated_list1;
+use nom::sequence::{delimited, terminated};
+use nom::IResult;
+
+/// Names of features defined by manifest.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub(crate) struct Features {
+    names: Vec<String>,
+}
+
+impl Features {
+    /// Reads feature names from manifest content.
+    pub(crate) fn parse(manifest: &str) -> Self {
+        let mut names = Vec::new();
+        let mut table = Vec::new();
+
+        for line in manifest.lines().map(str::trim) {
+            if line.starts_with('[') {
+                let header = line.trim_start_matches('[');
+                table = match Self::nom_parse_key(header) {
+                    Ok((_, key)) => key,
+                    Err(_) => Vec::new(),
+                };
+                continue;
+            }
+
+            let (value, key) = match terminated(Self::nom_parse_key, char('='))(line) {
+                Ok((value, key)) => (value.trim(), key),
+                Err(_) => continue,
+            };
+            let key = key.iter().map(String::as_str).collect::<Vec<_>>();
+            let table = table.iter().map(String::as_str).collect::<Vec<_>>();
+
+            match (table.as_slice(), key.as_slice()) {
+                (["features"], [name]) => names.push(name.to_string()),
+                (table, [name]) if Self::is_dependencies(table) && Self::is_optional_inline_table(value) => {
+                    names.push(name.to_string());
+                },
+                (table, [name, "optional"]) if Self::is_dependencies(table) && value.starts_with("true") => {
+                    names.push(name.to_string());
+                },
+                ([table @ .., name], ["optional"]) if Self::is_dependencies(table) && value.starts_with("true") => {
+                    names.push(name.to_string());
+                },
+                _ => {},
+            }
+        }
+
+        names.sort();
+        names.dedup();
+
+        Self { names }
+    }
+
+    /// Returns feature name as spelled in manifest for name of `CARGO_FEATURE_*` variable.
+    ///
+    /// Falls back to lowercase name when feature isn't defined by manifest.
+    pub(crate) fn name(&self, variable: &str) -> String {
+        self.names
+            .iter()
+            .find(|name| name.to_uppercase().replace('-', "_") == variable)
+            .cloned()
+            .unwrap_or_else(|| variable.to_lowercase())
+    }
+
+    /// Checks whether table contains dependencies which can be optional.
+    fn is_dependencies(table: &[&str]) -> bool {
+        matches!(
+            table,
+            ["dependencies" | "build-dependencies"] | ["target", _, "dependencies" | "build-dependencies"]
+        )
+    }
+
+    /// Checks whether value is an inline table with `optional = true` entry.
+    fn is_optional_inline_table(value: &str) -> bool {
+        let value = value.split_whitespace().collect::<String>();
+        value.starts_with('{') && value.contains("optional=true")
+    }
+
+    /// Parse dotted key followed by optional whitespaces.
+    fn nom_parse_key(input: &str) -> IResult<&str, Vec<String>, VerboseError<&str>> {
+        let bare = take_while1(|char: char| char.is_ascii_alphanumeric() || char == '-' || char == '_');
+        let basic = delimited(char('"'), is_not("\""), char('"'));
+        let literal = delimited(char('\''), is_not("'"), char('\''));
+        let simple = map(delimited(space0, alt((bare, basic, literal)), space0), str::to_string);
+
+        let parser = separated_list1(char('.'), simple);
+
+        context("key", parser)(input)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_features_parse() {
+        let features = Features::parse(
+            r#"
+[package]
+name = "crate"
+version = "0.1.0"
+
+[dependencies]
+serde = { version = "1.0.0", optional = true }
+chrono = { version = "0.4.23", default-features = false }
+rayon.version = "1.0.0"
+rayon.optional = true
+
+[dependencies.miniz_oxide]
+version = "0.8.0"
+optional = true
+
+[target.'cfg(unix)'.dependencies]
+libc = { version = "0.2.0", optional=true }
+
+[dev-dependencies]
+tempfile = { version = "3.3.0", optional = true }
+
+[features]
+default = ["std"]
+std = []
+"async-io" = ["dep:tokio"]
+sha1_smol = [
+    "dep:sha1_smol",
+]
+"#,
+        );
+        assert_eq!(
+            features.names,
+            vec![
+                "async-io",
+                "default",
+                "libc",
+                "miniz_oxide",
+                "rayon",
+                "serde",
+                "sha1_smol",
+                "std"
+            ]
+        );
+        assert_eq!(features.name("ASYNC_IO"), "async-io");
+        assert_eq!(features.name("SHA1_SMOL"), "sha1_smol");
+        assert_eq!(features.name("MINIZ_OXIDE"), "miniz_oxide");
+        assert_eq!(features.name("UNKNOWN_FEATURE"), "unknown_feature");
+        assert_eq!(Features::parse("").name("STD"), "std");
+    }
+}
@@ -0,0 +1,194 @@
+//! Package related types.
+
+use std::borrow::Cow;
+use std::fmt::{self, Display, Formatter};
+use std::result;
+use std::str::FromStr;
+
+use nom::bytes::complete::{tag, take_while1};
+use nom::character::complete::digit1;
+use nom::combinator::{all_consuming, map_res, opt};
+use nom::error::{context, VerboseError};
+use nom::sequence::{preceded, tuple};
+use nom::{Finish, IResult};
+
+use crate::error::Error;
+
+/// A package version, e.g. `1.2.3-alpha.1+build.5`.
+///
+/// Resources:
+/// * [Semantic Versioning 2.0.0](https://semver.org/spec/v2.0.0.html),
+/// * [The Cargo Book: The `version` field](https://doc.rust-lang.org/cargo/reference/manifest.html#the-version-field).
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PackageVersion {
+    major: u64,
+    minor: u64,
+    patch: u64,
+    pre: Option<Cow<'static, str>>,
+    build: Option<Cow<'static, str>>,
+}
+
+impl PackageVersion {
+    /// Creates a new package version.
+    #[inline]
+    #[must_use]
+    pub const fn new(
+        major: u64,
+        minor: u64,
+        patch: u64,
+        pre: Option<Cow<'static, str>>,
+        build: Option<Cow<'static, str>>,
+    ) -> Self {
+        Self {
+            major,
+            minor,
+            patch,
+            pre,
+            build,
+        }
+    }
+
+    /// Returns major version.
+    #[inline]
+    #[must_use]
+    pub const fn major(&self) -> u64 {
+        self.major
+    }
+
+    /// Returns minor version.
+    #[inline]
+    #[must_use]
+    pub const fn minor(&self) -> u64 {
+        self.minor
+    }
+
+    /// Returns patch version.
+    #[inline]
+    #[must_use]
+    pub const fn patch(&self) -> u64 {
+        self.patch
+    }
+
+    /// Returns pre-release identifiers, e.g. `alpha.1`.
+    #[inline]
+    #[must_use]
+    pub fn pre(&self) -> Option<&str> {
+        self.pre.as_deref()
+    }
+
+    /// Returns build metadata, e.g. `build.5`.
+    #[inline]
+    #[must_use]
+    pub fn build(&self) -> Option<&str> {
+        self.build.as_deref()
+    }
+
+    /// Parse package version.
+    fn nom_parse(input: &str) -> IResult<&str, Self, VerboseError<&str>> {
+        let identifiers = || take_while1(|char: char| char.is_ascii_alphanumeric() || char == '-' || char == '.');
+
+        let major = context("major", map_res(digit1, str::parse));
+        let minor = context("minor", preceded(tag("."), map_res(digit1, str::parse)));
+        let patch = context("patch", preceded(tag("."), map_res(digit1, str::parse)));
+        let pre = context("pre", opt(preceded(tag("-"), identifiers())));
+        let build = context("build", opt(preceded(tag("+"), identifiers())));
+
+        let parser = tuple((major, minor, patch, pre, build));
+
+        let (input, (major, minor, patch, pre, build)) = context("package-version", parser)(input)?;
+
+        let version = Self {
+            major,
+            minor,
+            patch,
+            pre: pre.map(|pre: &str| Cow::Owned(pre.to_string())),
+            build: build.map(|build: &str| Cow::Owned(build.to_string())),
+        };
+
+        Ok((input, version))
+    }
+}
+
+impl Display for PackageVersion {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let Self {
+            major,
+            minor,
+            patch,
+            pre,
+            build,
+        } = self;
+
+        write!(f, "{major}.{minor}.{patch}")?;
+        if let Some(pre) = pre {
+            write!(f, "-{pre}")?;
+        }
+        if let Some(build) = build {
+            write!(f, "+{build}")?;
+        }
+        Ok(())
+    }
+}
+
+impl FromStr for PackageVersion {
+    type Err = Error;
+
+    fn from_str(s: &str) -> result::Result<Self, Self::Err> {
+        let parser = all_consuming(Self::nom_parse);
+
+        let (_, version) = context("package-version", parser)(s).finish().map_err(|error| {
+            let errors = error
+                .errors
+                .into_iter()
+                .map(|(input, kind)| (input.to_string(), kind))
+                .collect();
+            let error = VerboseError { errors };
+            Error::Nom(error)
+        })?;
+
+        Ok(version)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use anyhow::Result;
+
+    use super::*;
+
+    #[test]
+    fn test_package_version_display() {
+        assert_eq!(format!("{}", PackageVersion::new(0, 0, 3, None, None)), "0.0.3");
+        assert_eq!(
+            format!(
+                "{}",
+                PackageVersion::new(1, 2, 3, Some(Cow::Borrowed("alpha.1")), Some(Cow::Borrowed("build.5")))
+            ),
+            "1.2.3-alpha.1+build.5"
+        );
+        assert_eq!(
+            format!("{}", PackageVersion::new(1, 2, 3, None, Some(Cow::Borrowed("x86-64")))),
+            "1.2.3+x86-64"
+        );
+    }
+
+    #[test]
+    fn test_package_version_from_str() -> Result<()> {
+        assert_eq!(
+            PackageVersion::from_str("0.0.3")?,
+            PackageVersion::new(0, 0, 3, None, None)
+        );
+        let version = PackageVersion::from_str("1.2.3-rc-1.2+build.5")?;
+        assert_eq!(version.major(), 1);
+        assert_eq!(version.minor(), 2);
+        assert_eq!(version.patch(), 3);
+        assert_eq!(version.pre(), Some("rc-1.2"));
+        assert_eq!(version.build(), Some("build.5"));
+        assert_eq!(version.to_string(), "1.2.3-rc-1.2+build.5");
+        assert!(PackageVersion::from_str("1.2").is_err());
+        assert!(PackageVersion::from_str("1.2.3-").is_err());
+        assert!(PackageVersion::from_str("1.2.3+").is_err());
+        assert!(PackageVersion::from_str("v1.2.3").is_err());
+        Ok(())
+    }
+}
@@ -1,59 +1,440 @@
 //! Rust related types.
 
+use std::borrow::Cow;
 use std::fmt::{self, Display, Formatter};
 use std::result;
 use std::str::FromStr;
 
 use chrono::NaiveDate;
 use nom::branch::alt;
-use nom::bytes::complete::tag;
-use nom::character::complete::{alpha1, digit1};
-use nom::combinator::{all_consuming, map_res, not, opt, peek, recognize};
+use nom::bytes::complete::{is_not, tag, take_while1};
+use nom::character::complete::{digit1, line_ending, not_line_ending};
+use nom::combinator::{all_consuming, map, map_opt, map_res, not, opt, peek, recognize};
 use nom::error::{context, VerboseError};
-use nom::sequence::{preceded, terminated, tuple};
+use nom::multi::{many0, separated_list1};
+use nom::sequence::{preceded, separated_pair, terminated, tuple};
 use nom::{Finish, IResult};
 
+#[cfg(feature = "script")]
+use crate::codegen::ConstExpr;
 use crate::error::Error;
 
-#[allow(non_camel_case_types)]
-#[cfg_attr(docsrs, doc(hidden))]
-#[derive(Clone, Copy, Debug, Eq, PartialEq)]
-#[non_exhaustive]
-pub enum Architecture {
-    // TODO: there are more architectures which are not supported yet
-    i686,
-    x86_64,
+/// Creates and displays a variant's payload which follows a dash-separated component.
+trait Suffix: Sized {
+    /// Creates payload from remaining tokens, returns `None` if they don't form a payload.
+    fn from_suffix(tokens: &[&str]) -> Option<Self>;
+
+    /// Formats payload.
+    fn fmt_suffix(&self, f: &mut Formatter<'_>) -> fmt::Result;
 }
 
-impl Architecture {
-    const I686_STR: &'static str = "i686";
-    const X86_64_STR: &'static str = "x86_64";
+impl Suffix for Option<Abi> {
+    fn from_suffix(tokens: &[&str]) -> Option<Self> {
+        match tokens {
+            [] => Some(None),
+            [abi] => Some(Some(Abi::from_tokens(&[abi]))),
+            _ => None,
+        }
+    }
 
-    /// Parse architecture.
-    fn nom_parse(input: &str) -> IResult<&str, Self, VerboseError<&str>> {
-        let i686 = context("i686", tag(Self::I686_STR));
-        let x86_64 = context("x86_64", tag(Self::X86_64_STR));
+    fn fmt_suffix(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Some(abi) => write!(f, "-{abi}"),
+            None => Ok(()),
+        }
+    }
+}
 
-        let mut parser = context("architecture", alt((i686, x86_64)));
+impl Suffix for LinuxAbi {
+    fn from_suffix(tokens: &[&str]) -> Option<Self> {
+        match tokens {
+            [abi] => Some(Self::from_tokens(&[abi])),
+            _ => None,
+        }
+    }
 
-        let (input, architecture) = parser(input)?;
+    fn fmt_suffix(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "-{self}")
+    }
+}
 
-        let architecture = match architecture {
-            Self::I686_STR => Self::i686,
-            Self::X86_64_STR => Self::x86_64,
-            _ => unreachable!(),
-        };
+impl Suffix for WindowsAbi {
+    fn from_suffix(tokens: &[&str]) -> Option<Self> {
+        match tokens {
+            [abi] => Some(Self::from_tokens(&[abi])),
+            _ => None,
+        }
+    }
 
-        Ok((input, architecture))
+    fn fmt_suffix(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "-{self}")
     }
 }
 
-impl Display for Architecture {
-    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
-        match self {
-            Self::i686 => write!(f, "{}", Self::I686_STR),
-            Self::x86_64 => write!(f, "{}", Self::X86_64_STR),
+/// Parse a single dash-separated token of target triple.
+fn nom_parse_token(input: &str) -> IResult<&str, &str, VerboseError<&str>> {
+    take_while1(|char| char != '-')(input)
+}
+
+/// Defines a component of target triple which is a single dash-separated token.
+///
+/// Components marked with `rest` consume all remaining tokens of target triple, variants with payload
+/// create it from tokens following the first one with [`Suffix`].
+///
+/// Unrecognised tokens are preserved as written by the `Other` variant.
+macro_rules! component {
+    (
+        $(#[$meta:meta])*
+        pub enum $name:ident($context:literal $(, $rest:ident)?) {
+            $(
+                $(#[$variant_meta:meta])*
+                $variant:ident $(($payload:ty))? => $string:literal,
+            )+
         }
+    ) => {
+        $(#[$meta])*
+        pub enum $name {
+            $(
+                $(#[$variant_meta])*
+                $variant $(($payload))?,
+            )+
+            /// Unrecognised component, preserved as written.
+            Other(Cow<'static, str>),
+        }
+
+        impl $name {
+            #[cfg(test)]
+            const STRINGS: &'static [&'static str] = &[$($string),+];
+
+            /// Checks if first token of component is recognised.
+            #[allow(dead_code)] // used only to tell vendor and system apart
+            fn is_known(token: &str) -> bool {
+                matches!(token, $($string)|+)
+            }
+
+            /// Creates component from dash-separated tokens, falls back to [`Self::Other`] if they aren't recognised.
+            fn from_tokens(tokens: &[&str]) -> Self {
+                let component = match tokens.first() {
+                    $(Some(&$string) => component!(@tokens tokens, $variant $(, $payload)?),)+
+                    _ => None,
+                };
+                component.unwrap_or_else(|| Self::Other(Cow::Owned(tokens.join("-"))))
+            }
+
+            /// Parse component.
+            fn nom_parse(input: &str) -> IResult<&str, Self, VerboseError<&str>> {
+                let parser = map(component!(@parse $($rest)?), |tokens| Self::from_tokens(&tokens));
+
+                context($context, parser)(input)
+            }
+        }
+
+        impl Display for $name {
+            fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+                match self {
+                    $(
+                        component!(@pattern $variant, payload $(, $payload)?) => {
+                            write!(f, "{}", $string)?;
+                            component!(@suffix f, payload $(, $payload)?)
+                        },
+                    )+
+                    Self::Other(other) => write!(f, "{other}"),
+                }
+            }
+        }
+
+        #[cfg(feature = "script")]
+        impl ConstExpr for $name {
+            fn const_expr(&self) -> String {
+                let path = concat!("::chksum_build::", stringify!($name));
+                match self {
+                    $(
+                        component!(@pattern $variant, payload $(, $payload)?) => {
+                            let variant = stringify!($variant);
+                            component!(@const_expr path, variant, payload $(, $payload)?)
+                        },
+                    )+
+                    Self::Other(other) => format!("{path}::Other({})", other.const_expr()),
+                }
+            }
+        }
+
+        impl FromStr for $name {
+            type Err = Error;
+
+            fn from_str(s: &str) -> result::Result<Self, Self::Err> {
+                let mut parser = context($context, all_consuming(Self::nom_parse));
+
+                let (_, component) = parser(s).finish().map_err(|error| {
+                    let errors = error
+                        .errors
+                        .into_iter()
+                        .map(|(input, kind)| (input.to_string(), kind))
+                        .collect();
+                    let error = VerboseError { errors };
+                    Error::Nom(error)
+                })?;
+
+                Ok(component)
+            }
+        }
+    };
+    (@parse) => {
+        map(nom_parse_token, |token| vec![token])
+    };
+    (@parse rest) => {
+        separated_list1(tag("-"), nom_parse_token)
+    };
+    (@tokens $tokens:ident, $variant:ident) => {
+        if $tokens.len() == 1 {
+            Some(Self::$variant)
+        } else {
+            None
+        }
+    };
+    (@tokens $tokens:ident, $variant:ident, $payload:ty) => {
+        <$payload as Suffix>::from_suffix(&$tokens[1..]).map(Self::$variant)
+    };
+    (@pattern $variant:ident, $binding:ident) => {
+        Self::$variant
+    };
+    (@pattern $variant:ident, $binding:ident, $payload:ty) => {
+        Self::$variant($binding)
+    };
+    (@const_expr $path:ident, $variant:ident, $binding:ident) => {
+        format!("{}::{}", $path, $variant)
+    };
+    (@const_expr $path:ident, $variant:ident, $binding:ident, $payload:ty) => {
+        format!("{}::{}({})", $path, $variant, $binding.const_expr())
+    };
+    (@suffix $f:ident, $binding:ident) => {
+        Ok(())
+    };
+    (@suffix $f:ident, $binding:ident, $payload:ty) => {
+        Suffix::fmt_suffix($binding, $f)
+    };
+}
+
+component! {
+    /// An ABI of target triple for systems other than Linux and Windows.
+    ///
+    /// Used by variants of [`System`], e.g. `eabihf` in `armv7-unknown-netbsd-eabihf`.
+    #[allow(clippy::upper_case_acronyms)]
+    #[derive(Clone, Debug, Eq, PartialEq)]
+    #[non_exhaustive]
+    pub enum Abi("abi") {
+        /// Embedded ABI.
+        EABI => "eabi",
+        /// Embedded ABI with hardware floating point.
+        EABIHF => "eabihf",
+        /// ELF binaries.
+        ELF => "elf",
+        /// Freestanding environment.
+        Freestanding => "freestanding",
+        /// GNU C library.
+        GNU => "gnu",
+        /// Mac Catalyst.
+        MacABI => "macabi",
+        /// mlibc C library.
+        Mlibc => "mlibc",
+        /// Newlib C library with hardware floating point.
+        NewlibEABIHF => "newlibeabihf",
+        /// QNX Neutrino 7.0.
+        QNX700 => "qnx700",
+        /// QNX Neutrino 7.1.
+        QNX710 => "qnx710",
+        /// QNX Neutrino 7.1 with `io-sock` network stack.
+        QNX710IOSock => "qnx710_iosock",
+        /// QNX Neutrino 8.0.
+        QNX800 => "qnx800",
+        /// Intel Software Guard Extensions.
+        SGX => "sgx",
+        /// Simulator.
+        Sim => "sim",
+        /// Software floating point.
+        SoftFloat => "softfloat",
+        /// Signal Processing Engine.
+        SPE => "spe",
+        /// Threads support.
+        Threads => "threads",
+        /// uClibc C library.
+        UClibc => "uclibc",
+    }
+}
+
+component! {
+    /// An architecture of target triple.
+    ///
+    /// Resources:
+    /// * [The rustc book: Platform Support](https://doc.rust-lang.org/rustc/platform-support.html).
+    #[allow(non_camel_case_types)]
+    #[derive(Clone, Debug, Eq, PartialEq)]
+    #[non_exhaustive]
+    pub enum Architecture("architecture") {
+        /// 64-bit ARM.
+        aarch64 => "aarch64",
+        /// 64-bit ARM, big-endian.
+        aarch64_be => "aarch64_be",
+        /// 64-bit ARMv8-R.
+        aarch64v8r => "aarch64v8r",
+        /// AMD GPU.
+        amdgcn => "amdgcn",
+        /// 32-bit ARM.
+        arm => "arm",
+        /// 64-bit ARM with 32-bit pointers.
+        arm64_32 => "arm64_32",
+        /// 64-bit ARM with pointer authentication.
+        arm64e => "arm64e",
+        /// 64-bit ARM Emulation Compatible.
+        arm64ec => "arm64ec",
+        /// 32-bit ARM, big-endian.
+        armeb => "armeb",
+        /// ARMv7-R, big-endian.
+        armebv7r => "armebv7r",
+        /// ARMv4T.
+        armv4t => "armv4t",
+        /// ARMv5TE.
+        armv5te => "armv5te",
+        /// ARMv6.
+        armv6 => "armv6",
+        /// ARMv6K.
+        armv6k => "armv6k",
+        /// ARMv7.
+        armv7 => "armv7",
+        /// ARMv7-A.
+        armv7a => "armv7a",
+        /// ARMv7-A for Apple Watch.
+        armv7k => "armv7k",
+        /// ARMv7-R.
+        armv7r => "armv7r",
+        /// ARMv7-A for Apple A6.
+        armv7s => "armv7s",
+        /// ARMv8-R.
+        armv8r => "armv8r",
+        /// AVR.
+        avr => "avr",
+        /// BPF, big-endian.
+        bpfeb => "bpfeb",
+        /// BPF, little-endian.
+        bpfel => "bpfel",
+        /// C-SKY.
+        csky => "csky",
+        /// Hexagon.
+        hexagon => "hexagon",
+        /// 32-bit x86 (i386).
+        i386 => "i386",
+        /// 32-bit x86 (i586).
+        i586 => "i586",
+        /// 32-bit x86 (i686).
+        i686 => "i686",
+        /// 32-bit LoongArch.
+        loongarch32 => "loongarch32",
+        /// 64-bit LoongArch.
+        loongarch64 => "loongarch64",
+        /// Motorola 68000.
+        m68k => "m68k",
+        /// 32-bit MIPS, big-endian.
+        mips => "mips",
+        /// 64-bit MIPS, big-endian.
+        mips64 => "mips64",
+        /// 64-bit MIPS, little-endian.
+        mips64el => "mips64el",
+        /// 32-bit MIPS, little-endian.
+        mipsel => "mipsel",
+        /// 32-bit MIPS Release 6, big-endian.
+        mipsisa32r6 => "mipsisa32r6",
+        /// 32-bit MIPS Release 6, little-endian.
+        mipsisa32r6el => "mipsisa32r6el",
+        /// 64-bit MIPS Release 6, big-endian.
+        mipsisa64r6 => "mipsisa64r6",
+        /// 64-bit MIPS Release 6, little-endian.
+        mipsisa64r6el => "mipsisa64r6el",
+        /// 16-bit MSP430.
+        msp430 => "msp430",
+        /// 64-bit NVIDIA PTX.
+        nvptx64 => "nvptx64",
+        /// 32-bit PowerPC.
+        powerpc => "powerpc",
+        /// 64-bit PowerPC, big-endian.
+        powerpc64 => "powerpc64",
+        /// 64-bit PowerPC, little-endian.
+        powerpc64le => "powerpc64le",
+        /// 32-bit RISC-V.
+        riscv32 => "riscv32",
+        /// 32-bit RISC-V with E extension.
+        riscv32e => "riscv32e",
+        /// 32-bit RISC-V with E and M extensions.
+        riscv32em => "riscv32em",
+        /// 32-bit RISC-V with E, M and C extensions.
+        riscv32emc => "riscv32emc",
+        /// 32-bit RISC-V with G and C extensions.
+        riscv32gc => "riscv32gc",
+        /// 32-bit RISC-V with I extension.
+        riscv32i => "riscv32i",
+        /// 32-bit RISC-V with I and M extensions.
+        riscv32im => "riscv32im",
+        /// 32-bit RISC-V with I, M and A extensions.
+        riscv32ima => "riscv32ima",
+        /// 32-bit RISC-V with I, M, A and C extensions.
+        riscv32imac => "riscv32imac",
+        /// 32-bit RISC-V with I, M, A, F and C extensions.
+        riscv32imafc => "riscv32imafc",
+        /// 32-bit RISC-V with I, M and C extensions.
+        riscv32imc => "riscv32imc",
+        /// 64-bit RISC-V.
+        riscv64 => "riscv64",
+        /// 64-bit RISC-V with RVA23 profile.
+        riscv64a23 => "riscv64a23",
+        /// 64-bit RISC-V with G and C extensions.
+        riscv64gc => "riscv64gc",
+        /// 64-bit RISC-V with I and M extensions.
+        riscv64im => "riscv64im",
+        /// 64-bit RISC-V with I, M, A and C extensions.
+        riscv64imac => "riscv64imac",
+        /// IBM Z.
+        s390x => "s390x",
+        /// 32-bit SPARC.
+        sparc => "sparc",
+        /// 64-bit SPARC.
+        sparc64 => "sparc64",
+        /// 64-bit SPARC V9.
+        sparcv9 => "sparcv9",
+        /// ARMv4T Thumb.
+        thumbv4t => "thumbv4t",
+        /// ARMv5TE Thumb.
+        thumbv5te => "thumbv5te",
+        /// ARMv6 Thumb.
+        thumbv6 => "thumbv6",
+        /// ARMv6-M Thumb.
+        thumbv6m => "thumbv6m",
+        /// ARMv7-A Thumb.
+        thumbv7a => "thumbv7a",
+        /// ARMv7E-M Thumb.
+        thumbv7em => "thumbv7em",
+        /// ARMv7-M Thumb.
+        thumbv7m => "thumbv7m",
+        /// ARMv7-A Thumb with NEON.
+        thumbv7neon => "thumbv7neon",
+        /// ARMv7-R Thumb.
+        thumbv7r => "thumbv7r",
+        /// ARMv8-M Baseline Thumb.
+        thumbv8m_base => "thumbv8m.base",
+        /// ARMv8-M Mainline Thumb.
+        thumbv8m_main => "thumbv8m.main",
+        /// ARMv8-R Thumb.
+        thumbv8r => "thumbv8r",
+        /// 32-bit WebAssembly.
+        wasm32 => "wasm32",
+        /// 32-bit WebAssembly 1.0.
+        wasm32v1 => "wasm32v1",
+        /// 64-bit WebAssembly.
+        wasm64 => "wasm64",
+        /// 64-bit x86.
+        x86_64 => "x86_64",
+        /// 64-bit x86 Haswell.
+        x86_64h => "x86_64h",
+        /// Xtensa.
+        xtensa => "xtensa",
     }
 }
 
@@ -202,6 +583,43 @@ impl ChannelVersion {
     }
 }
 
+#[cfg(feature = "script")]
+impl ChannelVersion {
+    /// Returns major version number.
+    pub(crate) const fn major(self) -> usize {
+        match self {
+            Self::MajorMinor(major, _) | Self::MajorMinorPatch(major, _, _) => major,
+        }
+    }
+
+    /// Returns minor version number.
+    pub(crate) const fn minor(self) -> usize {
+        match self {
+            Self::MajorMinor(_, minor) | Self::MajorMinorPatch(_, minor, _) => minor,
+        }
+    }
+}
+
+impl FromStr for ChannelVersion {
+    type Err = Error;
+
+    fn from_str(s: &str) -> result::Result<Self, Self::Err> {
+        let mut parser = context("version", all_consuming(Self::nom_parse));
+
+        let (_, version) = parser(s).finish().map_err(|error| {
+            let errors = error
+                .errors
+                .into_iter()
+                .map(|(input, kind)| (input.to_string(), kind))
+                .collect();
+            let error = VerboseError { errors };
+            Error::Nom(error)
+        })?;
+
+        Ok(version)
+    }
+}
+
 impl Display for ChannelVersion {
     fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
         match self {
@@ -211,24 +629,80 @@ impl Display for ChannelVersion {
     }
 }
 
-#[cfg_attr(docsrs, doc(hidden))]
-#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+/// A target triple, e.g. `x86_64-unknown-linux-gnu`.
+///
+/// Vendor is optional, e.g. `x86_64-linux-gnu`.
+///
+/// Unrecognised components are preserved by `Other` variants, e.g. `Architecture::Other("future64")` in
+/// `future64-unknown-linux-gnu`, so any dash-separated triple of non-empty components can be parsed.
+///
+/// Resources:
+/// * [The rustc book: Platform Support](https://doc.rust-lang.org/rustc/platform-support.html).
+#[derive(Clone, Debug, Eq, PartialEq)]
 pub struct Host {
-    pub(crate) architecture: Architecture,
-    pub(crate) vendor: Option<Vendor>,
-    pub(crate) system: System,
+    architecture: Architecture,
+    vendor: Option<Vendor>,
+    system: System,
 }
 
 impl Host {
+    /// Creates a new target triple.
+    #[inline]
+    #[must_use]
+    pub const fn new(architecture: Architecture, vendor: Option<Vendor>, system: System) -> Self {
+        Self {
+            architecture,
+            vendor,
+            system,
+        }
+    }
+
+    /// Returns architecture.
+    #[inline]
+    #[must_use]
+    pub const fn architecture(&self) -> &Architecture {
+        &self.architecture
+    }
+
+    /// Returns vendor.
+    #[inline]
+    #[must_use]
+    pub const fn vendor(&self) -> Option<&Vendor> {
+        self.vendor.as_ref()
+    }
+
+    /// Returns system along with its ABI.
+    #[inline]
+    #[must_use]
+    pub const fn system(&self) -> &System {
+        &self.system
+    }
+
     /// Parse host.
     fn nom_parse(input: &str) -> IResult<&str, Self, VerboseError<&str>> {
-        let architecture = Architecture::nom_parse;
-        let vendor = opt(preceded(tag("-"), Vendor::nom_parse));
-        let system = preceded(tag("-"), System::nom_parse);
+        let parser = map_opt(separated_list1(tag("-"), nom_parse_token), |tokens| {
+            Self::from_tokens(&tokens)
+        });
 
-        let parser = tuple((architecture, vendor, system));
+        context("host", parser)(input)
+    }
 
-        let (input, (architecture, vendor, system)) = context("host", parser)(input)?;
+    /// Creates host from dash-separated tokens.
+    ///
+    /// Vendor is present if token following architecture is a known vendor or an unknown component followed by
+    /// a system, e.g. `x86_64-unknown-linux-gnu` or `x86_64-foo-bar`, otherwise that token starts a system, e.g.
+    /// `aarch64-linux-android`.
+    fn from_tokens(tokens: &[&str]) -> Option<Self> {
+        let (architecture, tokens) = tokens.split_first()?;
+        let (token, _) = tokens.split_first()?;
+
+        let architecture = Architecture::from_tokens(&[architecture]);
+        let has_vendor = tokens.len() > 1 && (Vendor::is_known(token) || !System::is_known(token));
+        let (vendor, system) = if has_vendor {
+            (Some(Vendor::from_tokens(&[token])), System::from_tokens(&tokens[1..]))
+        } else {
+            (None, System::from_tokens(tokens))
+        };
 
         let host = Self {
             architecture,
@@ -236,7 +710,7 @@ impl Host {
             system,
         };
 
-        Ok((input, host))
+        Some(host)
     }
 }
 
@@ -248,7 +722,7 @@ impl Display for Host {
             system,
         } = self;
 
-        let vendor = vendor.map_or_else(String::new, |vendor| format!("-{vendor}"));
+        let vendor = vendor.as_ref().map_or_else(String::new, |vendor| format!("-{vendor}"));
 
         write!(f, "{architecture}{vendor}-{system}")
     }
@@ -274,124 +748,371 @@ impl FromStr for Host {
     }
 }
 
-#[allow(clippy::upper_case_acronyms)]
-#[cfg_attr(docsrs, doc(hidden))]
-#[derive(Clone, Copy, Debug, Eq, PartialEq)]
-#[non_exhaustive]
-pub enum LinuxAbi {
-    // TODO: there are more ABIs which are not supported yet
-    GNU,
-    GNUX32,
-    MUSL,
+component! {
+    /// An ABI of Linux system.
+    ///
+    /// Used by [`System::Linux`] variant.
+    #[allow(clippy::upper_case_acronyms)]
+    #[derive(Clone, Debug, Eq, PartialEq)]
+    #[non_exhaustive]
+    pub enum LinuxAbi("linux-abi") {
+        /// Android.
+        Android => "android",
+        /// Android with embedded ABI.
+        AndroidEABI => "androideabi",
+        /// GNU C library.
+        GNU => "gnu",
+        /// GNU C library with ILP32 data model.
+        GNUILP32 => "gnu_ilp32",
+        /// GNU C library with N64 ABI.
+        GNUABI64 => "gnuabi64",
+        /// GNU C library with C-SKY ABI v2.
+        GNUABIV2 => "gnuabiv2",
+        /// GNU C library with C-SKY ABI v2 and hardware floating point.
+        GNUABIV2HF => "gnuabiv2hf",
+        /// GNU C library with AddressSanitizer.
+        GNUASan => "gnuasan",
+        /// GNU C library with embedded ABI.
+        GNUEABI => "gnueabi",
+        /// GNU C library with embedded ABI and hardware floating point.
+        GNUEABIHF => "gnueabihf",
+        /// GNU C library with Signal Processing Engine.
+        GNUSPE => "gnuspe",
+        /// GNU C library with x32 ABI.
+        GNUX32 => "gnux32",
+        /// musl C library.
+        MUSL => "musl",
+        /// musl C library with N64 ABI.
+        MUSLABI64 => "muslabi64",
+        /// musl C library with embedded ABI.
+        MUSLEABI => "musleabi",
+        /// musl C library with embedded ABI and hardware floating point.
+        MUSLEABIHF => "musleabihf",
+        /// musl C library with Signal Processing Engine.
+        MUSLSPE => "muslspe",
+        /// No C library.
+        NoLibc => "none",
+        /// OpenHarmony.
+        OHOS => "ohos",
+        /// uClibc C library.
+        UClibc => "uclibc",
+        /// uClibc C library with embedded ABI.
+        UClibcEABI => "uclibceabi",
+        /// uClibc C library with embedded ABI and hardware floating point.
+        UClibcEABIHF => "uclibceabihf",
+    }
 }
 
-impl LinuxAbi {
-    const GNUX32_STR: &'static str = "gnux32";
-    const GNU_STR: &'static str = "gnu";
-    const MUSL_STR: &'static str = "musl";
+/// A compiler version printed by `rustc -vV`.
+///
+/// Resources:
+/// * [The rustc book: Command-line Arguments](https://doc.rust-lang.org/rustc/command-line-arguments.html#-v---verbose-use-verbose-output),
+/// * [The Cargo Book: Environment variables Cargo sets for build scripts](https://doc.rust-lang.org/cargo/reference/environment-variables.html#environment-variables-cargo-sets-for-build-scripts).
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RustcVersion {
+    version: ChannelVersion,
+    channel: Channel,
+    commit_hash: Option<String>,
+    commit_date: Option<NaiveDate>,
+    host: Host,
+    llvm_version: Option<String>,
+}
 
-    /// Parse ABI.
-    fn nom_parse(input: &str) -> IResult<&str, Self, VerboseError<&str>> {
-        let gnu = terminated(tag(Self::GNU_STR), not(peek(alpha1)));
-        let gnux32 = tag(Self::GNUX32_STR);
-        let musl = tag(Self::MUSL_STR);
+impl RustcVersion {
+    const BETA_STR: &'static str = "beta";
+    const DEV_STR: &'static str = "dev";
+    const NIGHTLY_STR: &'static str = "nightly";
+    const UNKNOWN_STR: &'static str = "unknown";
 
-        let parser = alt((gnu, gnux32, musl));
+    /// Returns compiler version.
+    #[inline]
+    #[must_use]
+    pub const fn version(&self) -> ChannelVersion {
+        self.version
+    }
 
-        let (input, linux_abi) = context("linux-abi", parser)(input)?;
+    /// Returns compiler channel.
+    ///
+    /// Locally built compilers with `-dev` suffix are treated as [`Channel::Nightly`].
+    #[inline]
+    #[must_use]
+    pub const fn channel(&self) -> &Channel {
+        &self.channel
+    }
 
-        let linux_abi = match linux_abi {
-            Self::GNU_STR => Self::GNU,
-            Self::GNUX32_STR => Self::GNUX32,
-            Self::MUSL_STR => Self::MUSL,
-            _ => unreachable!(),
-        };
+    /// Returns commit hash of compiler if known.
+    #[inline]
+    #[must_use]
+    pub fn commit_hash(&self) -> Option<&str> {
+        self.commit_hash.as_deref()
+    }
 
-        Ok((input, linux_abi))
+    /// Returns commit date of compiler if known.
+    #[inline]
+    #[must_use]
+    pub const fn commit_date(&self) -> Option<&NaiveDate> {
+        self.commit_date.as_ref()
     }
-}
 
-impl Display for LinuxAbi {
-    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
-        match self {
-            Self::GNU => write!(f, "{}", Self::GNU_STR),
-            Self::GNUX32 => write!(f, "{}", Self::GNUX32_STR),
-            Self::MUSL => write!(f, "{}", Self::MUSL_STR),
-        }
+    /// Returns host triple of compiler.
+    #[inline]
+    #[must_use]
+    pub const fn host(&self) -> &Host {
+        &self.host
     }
-}
 
-#[cfg_attr(docsrs, doc(hidden))]
-#[derive(Clone, Copy, Debug, Eq, PartialEq)]
-#[non_exhaustive]
-pub enum System {
-    // TODO: there are more systems which are not supported yet
-    Darwin,
-    Linux(LinuxAbi),
-    Windows(WindowsAbi),
-}
+    /// Returns LLVM version of compiler if known.
+    #[inline]
+    #[must_use]
+    pub fn llvm_version(&self) -> Option<&str> {
+        self.llvm_version.as_deref()
+    }
 
-impl System {
-    const DARWIN_STR: &'static str = "darwin";
-    const LINUX_STR: &'static str = "linux";
-    const WINDOWS_STR: &'static str = "windows";
+    /// Parse `key: value` lines preceded by `rustc ...` line.
+    fn nom_parse(input: &str) -> IResult<&str, Vec<(&str, &str)>, VerboseError<&str>> {
+        let header = context(
+            "header",
+            terminated(preceded(tag("rustc "), not_line_ending), line_ending),
+        );
+        let field = separated_pair(is_not(":\r\n"), tag(": "), not_line_ending);
+        let fields = many0(terminated(field, opt(line_ending)));
 
-    /// Parse system.
-    fn nom_parse(input: &str) -> IResult<&str, Self, VerboseError<&str>> {
-        let darwin = tag(Self::DARWIN_STR);
-        let linux = tag(Self::LINUX_STR);
-        let windows = tag(Self::WINDOWS_STR);
+        let parser = preceded(header, fields);
 
-        let parser = alt((darwin, linux, windows));
+        context("rustc-version", parser)(input)
+    }
 
-        let (input, system) = context("system", parser)(input)?;
+    /// Parse release, e.g. `1.74.1`, `1.75.0-beta.7` or `1.76.0-nightly`.
+    fn nom_parse_release(input: &str) -> IResult<&str, (ChannelVersion, Channel), VerboseError<&str>> {
+        let version = ChannelVersion::nom_parse_major_minor_patch;
+        let beta = recognize(preceded(tag(Self::BETA_STR), opt(preceded(tag("."), digit1))));
+        let nightly = tag(Self::NIGHTLY_STR);
+        let dev = tag(Self::DEV_STR);
+        let channel = opt(preceded(tag("-"), alt((beta, nightly, dev))));
 
-        let (input, system) = match system {
-            Self::DARWIN_STR => (input, Self::Darwin),
-            Self::LINUX_STR => {
-                let linux_abi = LinuxAbi::nom_parse;
+        let parser = tuple((version, channel));
 
-                let parser = preceded(tag("-"), linux_abi);
+        let (input, (version, channel)) = context("release", parser)(input)?;
 
-                let (input, linux_abi) = context("system-abi", parser)(input)?;
+        let channel = match channel {
+            None => Channel::Stable,
+            Some(channel) if channel.starts_with(Self::BETA_STR) => Channel::Beta,
+            Some(_) => Channel::Nightly,
+        };
 
-                (input, Self::Linux(linux_abi))
-            },
-            Self::WINDOWS_STR => {
-                let windows_abi = WindowsAbi::nom_parse;
+        Ok((input, (version, channel)))
+    }
+}
 
-                let parser = preceded(tag("-"), windows_abi);
+impl FromStr for RustcVersion {
+    type Err = Error;
 
-                let (input, windows_abi) = context("system-abi", parser)(input)?;
+    fn from_str(s: &str) -> result::Result<Self, Self::Err> {
+        let mut parser = context("rustc-version", all_consuming(Self::nom_parse));
 
-                (input, Self::Windows(windows_abi))
-            },
-            _ => unreachable!(),
+        let (_, fields) = parser(s).finish().map_err(|error| {
+            let errors = error
+                .errors
+                .into_iter()
+                .map(|(input, kind)| (input.to_string(), kind))
+                .collect();
+            let error = VerboseError { errors };
+            Error::Nom(error)
+        })?;
+
+        let field = |name: &str| {
+            fields
+                .iter()
+                .find(|(key, _)| *key == name)
+                .map(|(_, value)| *value)
+                .filter(|value| *value != Self::UNKNOWN_STR)
+        };
+
+        let release = field("release").ok_or_else(|| Error::Rustc(String::from("missing release")))?;
+        let mut parser = context("release", all_consuming(Self::nom_parse_release));
+        let (_, (version, channel)) = parser(release).finish().map_err(|error| {
+            let errors = error
+                .errors
+                .into_iter()
+                .map(|(input, kind)| (input.to_string(), kind))
+                .collect();
+            let error = VerboseError { errors };
+            Error::Nom(error)
+        })?;
+        let commit_hash = field("commit-hash").map(ToString::to_string);
+        let commit_date = field("commit-date")
+            .map(|commit_date| NaiveDate::parse_from_str(commit_date, "%Y-%m-%d"))
+            .transpose()?;
+        let host = field("host").ok_or_else(|| Error::Rustc(String::from("missing host")))?;
+        let host = Host::from_str(host)?;
+        let llvm_version = field("LLVM version").map(ToString::to_string);
+
+        let rustc_version = Self {
+            version,
+            channel,
+            commit_hash,
+            commit_date,
+            host,
+            llvm_version,
         };
 
-        Ok((input, system))
+        Ok(rustc_version)
     }
 }
 
-impl Display for System {
-    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
-        match self {
-            Self::Darwin => write!(f, "{}", Self::DARWIN_STR),
-            Self::Linux(linux_abi) => write!(f, "{}-{linux_abi}", Self::LINUX_STR),
-            Self::Windows(windows_abi) => write!(f, "{}-{windows_abi}", Self::WINDOWS_STR),
-        }
+component! {
+    /// A system of target triple along with its ABI.
+    #[allow(clippy::upper_case_acronyms)]
+    #[derive(Clone, Debug, Eq, PartialEq)]
+    #[non_exhaustive]
+    pub enum System("system", rest) {
+        /// IBM AIX.
+        AIX => "aix",
+        /// AMD HSA runtime.
+        AMDHSA => "amdhsa",
+        /// Bare metal.
+        BareMetal(Option<Abi>) => "none",
+        /// NVIDIA CUDA.
+        CUDA => "cuda",
+        /// Cygwin.
+        Cygwin => "cygwin",
+        /// macOS.
+        Darwin => "darwin",
+        /// DragonFly BSD.
+        DragonFly => "dragonfly",
+        /// Emscripten.
+        Emscripten => "emscripten",
+        /// ESP-IDF.
+        ESPIDF => "espidf",
+        /// FreeBSD.
+        FreeBSD => "freebsd",
+        /// Fuchsia.
+        Fuchsia => "fuchsia",
+        /// Haiku.
+        Haiku => "haiku",
+        /// HelenOS.
+        HelenOS => "helenos",
+        /// Hermit.
+        Hermit => "hermit",
+        /// GNU Hurd.
+        Hurd(Option<Abi>) => "hurd",
+        /// illumos.
+        Illumos => "illumos",
+        /// iOS.
+        IOS(Option<Abi>) => "ios",
+        /// L4Re.
+        L4Re(Option<Abi>) => "l4re",
+        /// Linux with given ABI.
+        Linux(LinuxAbi) => "linux",
+        /// LynxOS-178.
+        LynxOS178 => "lynxos178",
+        /// Managarm.
+        Managarm(Option<Abi>) => "managarm",
+        /// Motor OS.
+        Motor => "motor",
+        /// NetBSD.
+        NetBSD(Option<Abi>) => "netbsd",
+        /// Nintendo 3DS.
+        Nintendo3DS => "3ds",
+        /// QNX Neutrino.
+        NTO(Option<Abi>) => "nto",
+        /// NuttX.
+        NuttX(Option<Abi>) => "nuttx",
+        /// OpenBSD.
+        OpenBSD => "openbsd",
+        /// PlayStation Portable.
+        PSP => "psp",
+        /// PlayStation.
+        PSX => "psx",
+        /// Qualcomm Real-Time OS.
+        QuRT => "qurt",
+        /// Redox.
+        Redox => "redox",
+        /// RTEMS.
+        RTEMS(Option<Abi>) => "rtems",
+        /// SOLID.
+        SolidAsp3(Option<Abi>) => "solid_asp3",
+        /// Solaris.
+        Solaris => "solaris",
+        /// Nintendo Switch.
+        Switch(Option<Abi>) => "switch",
+        /// TEEOS.
+        TEEOS => "teeos",
+        /// Trusty.
+        Trusty => "trusty",
+        /// tvOS.
+        TvOS(Option<Abi>) => "tvos",
+        /// UEFI.
+        UEFI => "uefi",
+        /// Unknown system.
+        Unknown(Option<Abi>) => "unknown",
+        /// VEX V5 Brain.
+        V5 => "v5",
+        /// visionOS.
+        VisionOS(Option<Abi>) => "visionos",
+        /// PlayStation Vita.
+        Vita(Option<Abi>) => "vita",
+        /// VxWorks.
+        VxWorks(Option<Abi>) => "vxworks",
+        /// WASI Preview 1.
+        WASIp1(Option<Abi>) => "wasip1",
+        /// WASI Preview 2.
+        WASIp2 => "wasip2",
+        /// WASI Preview 3.
+        WASIp3 => "wasip3",
+        /// watchOS.
+        WatchOS(Option<Abi>) => "watchos",
+        /// Windows with given ABI.
+        Windows(WindowsAbi) => "windows",
+        /// Xous.
+        Xous(Option<Abi>) => "xous",
+        /// RISC Zero zkVM.
+        ZkVM(Option<Abi>) => "zkvm",
     }
 }
 
-#[cfg_attr(docsrs, doc(hidden))]
+/// A rustup toolchain, e.g. `nightly-2023-01-02-x86_64-unknown-linux-gnu`.
+///
+/// Resources:
+/// * [The rustup book: Toolchain specification](https://rust-lang.github.io/rustup/concepts/toolchains.html#toolchain-specification).
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub struct Toolchain {
-    pub(crate) channel: Channel,
-    pub(crate) date: Option<NaiveDate>,
-    pub(crate) host: Option<Host>,
+    channel: Channel,
+    date: Option<NaiveDate>,
+    host: Option<Host>,
 }
 
 impl Toolchain {
+    const DATE_FORMAT: &'static str = "%Y-%m-%d";
+
+    /// Creates a new toolchain.
+    #[inline]
+    #[must_use]
+    pub const fn new(channel: Channel, date: Option<NaiveDate>, host: Option<Host>) -> Self {
+        Self { channel, date, host }
+    }
+
+    /// Returns channel.
+    #[inline]
+    #[must_use]
+    pub const fn channel(&self) -> &Channel {
+        &self.channel
+    }
+
+    /// Returns archive date.
+    #[inline]
+    #[must_use]
+    pub const fn date(&self) -> Option<&NaiveDate> {
+        self.date.as_ref()
+    }
+
+    /// Returns host triple.
+    #[inline]
+    #[must_use]
+    pub const fn host(&self) -> Option<&Host> {
+        self.host.as_ref()
+    }
+
     /// Parse toolchain.
     fn nom_parse(input: &str) -> IResult<&str, Self, VerboseError<&str>> {
         let channel = Channel::nom_parse;
@@ -416,7 +1137,7 @@ impl Toolchain {
         let parser = recognize(tuple((year, tag("-"), month, tag("-"), day)));
 
         map_res(context("date", parser), |date| {
-            NaiveDate::parse_from_str(date, "%Y-%m-%d")
+            NaiveDate::parse_from_str(date, Self::DATE_FORMAT)
         })(input)
     }
 }
@@ -425,8 +1146,8 @@ impl Display for Toolchain {
     fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
         let Self { channel, date, host } = self;
 
-        let date = date.map_or_else(String::new, |date| format!("{date}"));
-        let host = host.map_or_else(String::new, |host| format!("{host}"));
+        let date = date.map_or_else(String::new, |date| format!("-{}", date.format(Self::DATE_FORMAT)));
+        let host = host.as_ref().map_or_else(String::new, |host| format!("-{host}"));
 
         write!(f, "{channel}{date}{host}")
     }
@@ -452,104 +1173,328 @@ impl FromStr for Toolchain {
     }
 }
 
-#[cfg_attr(docsrs, doc(hidden))]
-#[derive(Clone, Copy, Debug, Eq, PartialEq)]
-#[non_exhaustive]
-pub enum Vendor {
-    // TODO: there are more vendors which are not supported yet
-    Apple,
-    PC,
-    Unknown,
+component! {
+    /// A vendor of target triple.
+    #[allow(clippy::upper_case_acronyms)]
+    #[derive(Clone, Debug, Eq, PartialEq)]
+    #[non_exhaustive]
+    pub enum Vendor("vendor") {
+        /// AMD.
+        AMD => "amd",
+        /// Apple.
+        Apple => "apple",
+        /// Espressif.
+        ESP => "esp",
+        /// Espressif ESP32.
+        ESP32 => "esp32",
+        /// Espressif ESP32-S2.
+        ESP32S2 => "esp32s2",
+        /// Espressif ESP32-S3.
+        ESP32S3 => "esp32s3",
+        /// Fortanix.
+        Fortanix => "fortanix",
+        /// IBM.
+        IBM => "ibm",
+        /// Kyoto Microcomputer.
+        KMC => "kmc",
+        /// Lynx Software Technologies.
+        Lynx => "lynx",
+        /// MIPS Technologies.
+        MTI => "mti",
+        /// Nintendo.
+        Nintendo => "nintendo",
+        /// NVIDIA.
+        NVIDIA => "nvidia",
+        /// OpenWrt.
+        OpenWrt => "openwrt",
+        /// Generic PC.
+        PC => "pc",
+        /// RISC Zero.
+        Risc0 => "risc0",
+        /// Sony.
+        Sony => "sony",
+        /// Sun Microsystems.
+        Sun => "sun",
+        /// Unikraft.
+        Unikraft => "unikraft",
+        /// Unknown vendor.
+        Unknown => "unknown",
+        /// Universal Windows Platform.
+        UWP => "uwp",
+        /// VEX Robotics.
+        Vex => "vex",
+        /// WebAssembly Linux Interface.
+        Wali => "wali",
+        /// Windows 7 compatible.
+        Win7 => "win7",
+        /// Wind River Systems.
+        WRS => "wrs",
+    }
 }
 
-impl Vendor {
-    const APPLE_STR: &'static str = "apple";
-    const PC_STR: &'static str = "pc";
-    const UNKNOWN_STR: &'static str = "unknown";
+component! {
+    /// An ABI of Windows system.
+    ///
+    /// Used by [`System::Windows`] variant.
+    #[allow(clippy::upper_case_acronyms)]
+    #[derive(Clone, Debug, Eq, PartialEq)]
+    #[non_exhaustive]
+    pub enum WindowsAbi("windows-abi") {
+        /// MinGW toolchain.
+        GNU => "gnu",
+        /// MinGW toolchain with LLVM.
+        GNULLVM => "gnullvm",
+        /// Microsoft Visual C++ toolchain.
+        MSVC => "msvc",
+    }
+}
 
-    /// Parse vendor.
-    fn nom_parse(input: &str) -> IResult<&str, Self, VerboseError<&str>> {
-        let apple = tag(Self::APPLE_STR);
-        let pc = tag(Self::PC_STR);
-        let unknown = tag(Self::UNKNOWN_STR);
+#[cfg(test)]
+mod tests {
+    use anyhow::Result;
+    use proptest::prelude::*;
 
-        let parser = alt((apple, pc, unknown));
+    use super::*;
 
-        let (input, vendor) = context("vendor", parser)(input)?;
+    /// Output of `rustc --print target-list`.
+    const TARGET_LIST: &str = include_str!("../tests/fixtures/target-list.txt");
 
-        let vendor = match vendor {
-            Self::APPLE_STR => Self::Apple,
-            Self::PC_STR => Self::PC,
-            Self::UNKNOWN_STR => Self::Unknown,
-            _ => unreachable!(),
-        };
+    fn host() -> impl Strategy<Value = Host> {
+        let targets = TARGET_LIST.lines().collect::<Vec<_>>();
+        proptest::sample::select(targets).prop_map(|target| Host::from_str(target).expect("target is supported"))
+    }
 
-        Ok((input, vendor))
+    fn channel_version() -> impl Strategy<Value = ChannelVersion> {
+        prop_oneof![
+            (0..100usize, 0..1000usize).prop_map(|(major, minor)| ChannelVersion::MajorMinor(major, minor)),
+            (0..100usize, 0..1000usize, 0..100usize)
+                .prop_map(|(major, minor, patch)| ChannelVersion::MajorMinorPatch(major, minor, patch)),
+        ]
     }
-}
 
-impl Display for Vendor {
-    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
-        match self {
-            Self::Apple => write!(f, "{}", Self::APPLE_STR),
-            Self::PC => write!(f, "{}", Self::PC_STR),
-            Self::Unknown => write!(f, "{}", Self::UNKNOWN_STR),
-        }
+    fn channel() -> impl Strategy<Value = Channel> {
+        prop_oneof![
+            Just(Channel::Stable),
+            Just(Channel::Beta),
+            Just(Channel::Nightly),
+            channel_version().prop_map(Channel::Version),
+        ]
     }
-}
 
-#[allow(clippy::upper_case_acronyms)]
-#[cfg_attr(docsrs, doc(hidden))]
-#[derive(Clone, Copy, Debug, Eq, PartialEq)]
-#[non_exhaustive]
-pub enum WindowsAbi {
-    // TODO: there are more ABIs which are not supported yet
-    GNU,
-    GNULLVM,
-    MSVC,
-}
+    fn date() -> impl Strategy<Value = NaiveDate> {
+        (1970..10000i32, 1..=365u32)
+            .prop_map(|(year, ordinal)| NaiveDate::from_yo_opt(year, ordinal).expect("ordinal is valid in every year"))
+    }
 
-impl WindowsAbi {
-    const GNULLVM_STR: &'static str = "gnullvm";
-    const GNU_STR: &'static str = "gnu";
-    const MSVC_STR: &'static str = "msvc";
+    fn toolchain() -> impl Strategy<Value = Toolchain> {
+        (channel(), proptest::option::of(date()), proptest::option::of(host()))
+            .prop_map(|(channel, date, host)| Toolchain::new(channel, date, host))
+    }
 
-    /// Parse ABI.
-    fn nom_parse(input: &str) -> IResult<&str, Self, VerboseError<&str>> {
-        let gnu = terminated(tag(Self::GNU_STR), not(peek(alpha1)));
-        let gnullvm = tag(Self::GNULLVM_STR);
-        let msvc = tag(Self::MSVC_STR);
+    proptest! {
+        #[test]
+        fn test_host_round_trip(host in host()) {
+            prop_assert_eq!(Host::from_str(&host.to_string())?, host);
+        }
 
-        let parser = alt((gnu, gnullvm, msvc));
+        #[test]
+        fn test_host_from_str_never_fails(tokens in proptest::collection::vec("[a-z0-9_.]{1,10}", 2..=5)) {
+            let target = tokens.join("-");
+            prop_assert_eq!(Host::from_str(&target)?.to_string(), target);
+        }
 
-        let (input, windows_abi) = context("windows-abi", parser)(input)?;
+        #[test]
+        fn test_channel_round_trip(channel in channel()) {
+            prop_assert_eq!(Channel::from_str(&channel.to_string())?, channel);
+        }
 
-        let windows_abi = match windows_abi {
-            Self::GNU_STR => Self::GNU,
-            Self::GNULLVM_STR => Self::GNULLVM,
-            Self::MSVC_STR => Self::MSVC,
-            _ => unreachable!(),
-        };
+        #[test]
+        fn test_toolchain_round_trip(toolchain in toolchain()) {
+            prop_assert_eq!(Toolchain::from_str(&toolchain.to_string())?, toolchain);
+        }
+    }
 
-        Ok((input, windows_abi))
+    #[test]
+    fn test_components_round_trip() -> Result<()> {
+        for string in Abi::STRINGS {
+            assert_eq!(Abi::from_str(string)?.to_string(), *string);
+        }
+        for string in Architecture::STRINGS {
+            assert_eq!(Architecture::from_str(string)?.to_string(), *string);
+        }
+        for string in LinuxAbi::STRINGS {
+            assert_eq!(LinuxAbi::from_str(string)?.to_string(), *string);
+        }
+        for string in Vendor::STRINGS {
+            assert_eq!(Vendor::from_str(string)?.to_string(), *string);
+        }
+        for string in WindowsAbi::STRINGS {
+            assert_eq!(WindowsAbi::from_str(string)?.to_string(), *string);
+        }
+        for string in System::STRINGS {
+            let is_used = TARGET_LIST
+                .lines()
+                .any(|target| target.split('-').any(|component| component == *string));
+            assert!(is_used, "system {string} isn't used by any target");
+        }
+        assert_eq!(
+            Architecture::from_str("x86")?,
+            Architecture::Other(Cow::Borrowed("x86"))
+        );
+        assert!(Architecture::from_str("").is_err());
+        assert!(Vendor::from_str("apple-darwin").is_err());
+        assert_eq!(System::from_str("linux")?, System::Other(Cow::Borrowed("linux")));
+        assert_eq!(System::from_str("linux-gnu")?, System::Linux(LinuxAbi::GNU));
+        assert_eq!(System::from_str("none")?, System::BareMetal(None));
+        assert_eq!(System::from_str("none-eabihf")?, System::BareMetal(Some(Abi::EABIHF)));
+        assert_eq!(
+            System::from_str("darwin-sim")?,
+            System::Other(Cow::Borrowed("darwin-sim"))
+        );
+        assert_eq!(
+            System::from_str("linux-gnu_future")?,
+            System::Linux(LinuxAbi::Other(Cow::Borrowed("gnu_future")))
+        );
+        assert_eq!(
+            System::from_str("none-future")?,
+            System::BareMetal(Some(Abi::Other(Cow::Borrowed("future"))))
+        );
+        assert!(System::from_str("linux-").is_err());
+        Ok(())
     }
-}
 
-impl Display for WindowsAbi {
-    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
-        match self {
-            Self::GNU => write!(f, "{}", Self::GNU_STR),
-            Self::GNULLVM => write!(f, "{}", Self::GNULLVM_STR),
-            Self::MSVC => write!(f, "{}", Self::MSVC_STR),
+    #[test]
+    fn test_target_list_from_str() -> Result<()> {
+        for target in TARGET_LIST.lines() {
+            let host = Host::from_str(target)?;
+            assert_eq!(host.to_string(), target);
+            // `Debug` shows components nested in system, e.g. `Linux(Other("gnu_future"))`
+            assert!(
+                !format!("{host:?}").contains("Other("),
+                "{target} has unrecognised component: {host:?}"
+            );
         }
+        assert_eq!(
+            Host::from_str("aarch64-unknown-linux-gnu")?,
+            Host::new(
+                Architecture::aarch64,
+                Some(Vendor::Unknown),
+                System::Linux(LinuxAbi::GNU)
+            )
+        );
+        assert_eq!(
+            Host::from_str("thumbv8m.main-none-eabihf")?,
+            Host::new(Architecture::thumbv8m_main, None, System::BareMetal(Some(Abi::EABIHF)))
+        );
+        assert_eq!(
+            Host::from_str("wasm32-wasip1-threads")?,
+            Host::new(Architecture::wasm32, None, System::WASIp1(Some(Abi::Threads)))
+        );
+        assert_eq!(
+            Host::from_str("wasm32-unknown-unknown")?,
+            Host::new(Architecture::wasm32, Some(Vendor::Unknown), System::Unknown(None))
+        );
+        assert_eq!(
+            Host::from_str("x86_64-fortanix-unknown-sgx")?,
+            Host::new(
+                Architecture::x86_64,
+                Some(Vendor::Fortanix),
+                System::Unknown(Some(Abi::SGX))
+            )
+        );
+        assert_eq!(
+            Host::from_str("aarch64-linux-android")?,
+            Host::new(Architecture::aarch64, None, System::Linux(LinuxAbi::Android))
+        );
+        assert!(Host::from_str("x86_64").is_err());
+        assert!(Host::from_str("x86_64-").is_err());
+        assert!(Host::from_str("x86_64--linux-gnu").is_err());
+        Ok(())
     }
-}
 
-#[cfg(test)]
-mod tests {
-    use anyhow::Result;
+    #[test]
+    fn test_host_from_str_unknown_components() -> Result<()> {
+        assert_eq!(
+            Host::from_str("x86_64-unknown")?,
+            Host::new(Architecture::x86_64, None, System::Unknown(None))
+        );
+        assert_eq!(
+            Host::from_str("x86_64-unknown-linux")?,
+            Host::new(
+                Architecture::x86_64,
+                Some(Vendor::Unknown),
+                System::Other(Cow::Borrowed("linux"))
+            )
+        );
+        assert_eq!(
+            Host::from_str("future64-unknown-linux-gnu")?,
+            Host::new(
+                Architecture::Other(Cow::Borrowed("future64")),
+                Some(Vendor::Unknown),
+                System::Linux(LinuxAbi::GNU)
+            )
+        );
+        assert_eq!(
+            Host::from_str("aarch64-acme-linux-gnu")?,
+            Host::new(
+                Architecture::aarch64,
+                Some(Vendor::Other(Cow::Borrowed("acme"))),
+                System::Linux(LinuxAbi::GNU)
+            )
+        );
+        assert_eq!(
+            Host::from_str("aarch64-linux-future")?,
+            Host::new(
+                Architecture::aarch64,
+                None,
+                System::Linux(LinuxAbi::Other(Cow::Borrowed("future")))
+            )
+        );
+        assert_eq!(
+            Host::from_str("riscv64-unknown-futureos-gnu")?,
+            Host::new(
+                Architecture::riscv64,
+                Some(Vendor::Unknown),
+                System::Other(Cow::Borrowed("futureos-gnu"))
+            )
+        );
+        assert_eq!(
+            Host::from_str("riscv64-futureos")?,
+            Host::new(Architecture::riscv64, None, System::Other(Cow::Borrowed("futureos")))
+        );
+        assert_eq!(
+            Toolchain::from_str("nightly-2023-01-02-future64-acme-futureos")?,
+            Toolchain::new(
+                Channel::Nightly,
+                NaiveDate::from_ymd_opt(2023, 1, 2),
+                Some(Host::new(
+                    Architecture::Other(Cow::Borrowed("future64")),
+                    Some(Vendor::Other(Cow::Borrowed("acme"))),
+                    System::Other(Cow::Borrowed("futureos"))
+                ))
+            )
+        );
+        Ok(())
+    }
 
-    use super::*;
+    #[test]
+    fn test_toolchain_display() {
+        let host = Host::new(
+            Architecture::x86_64,
+            Some(Vendor::Unknown),
+            System::Linux(LinuxAbi::GNU),
+        );
+        let date = NaiveDate::from_ymd_opt(2023, 1, 2);
+        assert_eq!(Toolchain::new(Channel::Stable, None, None).to_string(), "stable");
+        assert_eq!(
+            Toolchain::new(Channel::Nightly, date, Some(host.clone())).to_string(),
+            "nightly-2023-01-02-x86_64-unknown-linux-gnu"
+        );
+        assert_eq!(
+            Toolchain::new(Channel::Version(ChannelVersion::MajorMinor(1, 70)), None, Some(host)).to_string(),
+            "1.70-x86_64-unknown-linux-gnu"
+        );
+    }
 
     #[test]
     fn test_channel_display() {
@@ -588,6 +1533,18 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn test_channel_version_from_str() -> Result<()> {
+        assert_eq!(ChannelVersion::from_str("1.74")?, ChannelVersion::MajorMinor(1, 74));
+        assert_eq!(
+            ChannelVersion::from_str("1.74.1")?,
+            ChannelVersion::MajorMinorPatch(1, 74, 1)
+        );
+        assert!(ChannelVersion::from_str("1").is_err());
+        assert!(ChannelVersion::from_str("1.74.1-nightly").is_err());
+        Ok(())
+    }
+
     #[test]
     fn test_host_from_str() -> Result<()> {
         assert_eq!(
@@ -689,6 +1646,72 @@ mod tests {
         Ok(())
     }
 
+    #[cfg(feature = "script")]
+    #[test]
+    fn test_channel_version_major_minor() {
+        assert_eq!(ChannelVersion::MajorMinorPatch(1, 74, 1).major(), 1);
+        assert_eq!(ChannelVersion::MajorMinorPatch(1, 74, 1).minor(), 74);
+        assert_eq!(ChannelVersion::MajorMinor(2, 0).major(), 2);
+        assert_eq!(ChannelVersion::MajorMinor(2, 0).minor(), 0);
+    }
+
+    #[test]
+    fn test_rustc_version_from_str() -> Result<()> {
+        let rustc_version = RustcVersion::from_str(
+            "rustc 1.74.1 (a28077b28 2023-12-04)
+binary: rustc
+commit-hash: a28077b28a02b92985b3a3faecf92813155f1ea1
+commit-date: 2023-12-04
+host: x86_64-unknown-linux-gnu
+release: 1.74.1
+LLVM version: 17.0.4
+",
+        )?;
+        assert_eq!(rustc_version.version(), ChannelVersion::MajorMinorPatch(1, 74, 1));
+        assert_eq!(rustc_version.channel(), &Channel::Stable);
+        assert_eq!(
+            rustc_version.commit_hash(),
+            Some("a28077b28a02b92985b3a3faecf92813155f1ea1")
+        );
+        assert_eq!(
+            rustc_version.commit_date(),
+            NaiveDate::from_ymd_opt(2023, 12, 4).as_ref()
+        );
+        assert_eq!(rustc_version.host(), &Host::from_str("x86_64-unknown-linux-gnu")?);
+        assert_eq!(rustc_version.llvm_version(), Some("17.0.4"));
+
+        let rustc_version = RustcVersion::from_str(
+            "rustc 1.75.0-beta.7 (1b4fe3ff5 2023-12-19)\r\nhost: aarch64-apple-darwin\r\nrelease: 1.75.0-beta.7\r\n",
+        )?;
+        assert_eq!(rustc_version.version(), ChannelVersion::MajorMinorPatch(1, 75, 0));
+        assert_eq!(rustc_version.channel(), &Channel::Beta);
+        assert_eq!(rustc_version.host().system(), &System::Darwin);
+        assert_eq!(rustc_version.commit_hash(), None);
+        assert_eq!(rustc_version.llvm_version(), None);
+
+        let rustc_version = RustcVersion::from_str(
+            "rustc 1.76.0-nightly
+binary: rustc
+commit-hash: unknown
+commit-date: unknown
+host: riscv64gc-unknown-linux-gnu
+release: 1.76.0-nightly",
+        )?;
+        assert_eq!(rustc_version.channel(), &Channel::Nightly);
+        assert_eq!(rustc_version.commit_hash(), None);
+        assert_eq!(rustc_version.commit_date(), None);
+
+        let rustc_version =
+            RustcVersion::from_str("rustc 1.80.0-dev\nhost: x86_64-unknown-linux-gnu\nrelease: 1.80.0-dev\n")?;
+        assert_eq!(rustc_version.channel(), &Channel::Nightly);
+
+        assert!(RustcVersion::from_str("rustc 1.74.1\nhost: x86_64-unknown-linux-gnu\n").is_err());
+        assert!(RustcVersion::from_str("rustc 1.74.1\nrelease: 1.74.1\n").is_err());
+        assert!(RustcVersion::from_str("rustc 1.74.1\nhost: x86_64-unknown-linux-gnu\nrelease: 1.74\n").is_err());
+        assert!(RustcVersion::from_str("cargo 1.74.1\nhost: x86_64-unknown-linux-gnu\nrelease: 1.74.1\n").is_err());
+        Ok(())
+    }
+
     #[test]
     fn test_toolchain_from_str() -> Result<()> {
         assert_eq!(
@@ -1,14 +1,22 @@
 //! Build script required items.
 
-use std::env;
-use std::fmt::Write;
+use std::fmt::{Display, Write};
+use std::ops::{Bound, RangeBounds};
+use std::path::Path;
+use std::process::Command;
 use std::str::FromStr;
+use std::{env, fs};
 
-use chrono::Local;
+use chrono::{DateTime, FixedOffset, Offset, SecondsFormat, TimeZone, Utc};
 
-use crate::cargo::Profile;
-use crate::error::Result;
-use crate::rust::{Channel, Toolchain};
+use crate::build::DatetimeSource;
+use crate::cargo::{BaseProfile, CargoVersion, Profile};
+use crate::codegen::{self, Values};
+use crate::error::{Error, Result};
+use crate::git::Head;
+use crate::manifest::Features;
+use crate::rust::{Channel, ChannelVersion, Host, RustcVersion, Toolchain};
+use crate::target::{Endian, Panic};
 
 /// Wraps [`BuildScript::setup`] to return [`anyhow::Result`] instead of [`Result`].
 ///
@@ -31,12 +39,306 @@ pub fn setup(build_script: &BuildScript) -> anyhow::Result<()> {
     Ok(())
 }
 
+/// A mode of build script section.
+///
+/// Converts from [`bool`] where `true` means [`Mode::Required`] and `false` means [`Mode::Disabled`].
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Mode {
+    /// Section is skipped.
+    Disabled,
+    /// Section is set up and any failure is returned as an error.
+    Required,
+    /// Section is set up and any failure is reported as a `cargo:warning`.
+    Optional,
+}
+
+impl From<bool> for Mode {
+    fn from(enabled: bool) -> Self {
+        if enabled {
+            Self::Required
+        } else {
+            Self::Disabled
+        }
+    }
+}
+
 /// Configuration for build script.
+///
+/// By default `build`, `cargo`, `rust` and `target` sections are required and `git` section is optional.
+///
+/// # Examples
+///
+/// ```rust,no_run
+/// use chksum_build::{BuildScript, Mode, Result};
+///
+/// fn main() -> Result<()> {
+///     BuildScript::new()
+///         .build(true)
+///         .cargo(true)
+///         .rust(Mode::Optional)
+///         .git(false)
+///         .setup()
+/// }
+/// ```
 #[allow(clippy::module_name_repetitions)]
-#[derive(Clone, Copy, Debug, Default)]
-pub struct BuildScript;
+#[derive(Clone, Copy, Debug)]
+pub struct BuildScript {
+    build: Mode,
+    cargo: Mode,
+    rust: Mode,
+    target: Mode,
+    git: Mode,
+    prefix: &'static str,
+    bare_cfg: bool,
+    rust_version_cfg: (Bound<usize>, Bound<usize>),
+    fixed_datetime: Option<DateTime<FixedOffset>>,
+    git_commit_datetime: bool,
+    offset: Option<FixedOffset>,
+    build_info_file: bool,
+}
+
+impl Default for BuildScript {
+    fn default() -> Self {
+        Self::new()
+    }
+}
 
 impl BuildScript {
+    const DEFAULT_PREFIX: &'static str = "CHKSUM_BUILD_INFO";
+    const GIT_SHORT_HASH_LENGTH: usize = 7;
+
+    /// Creates default configuration.
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            build: Mode::Required,
+            cargo: Mode::Required,
+            rust: Mode::Required,
+            target: Mode::Required,
+            git: Mode::Optional,
+            prefix: Self::DEFAULT_PREFIX,
+            bare_cfg: false,
+            rust_version_cfg: (Bound::Unbounded, Bound::Unbounded),
+            fixed_datetime: None,
+            git_commit_datetime: false,
+            offset: None,
+            build_info_file: true,
+        }
+    }
+
+    /// Sets mode of `build` section.
+    ///
+    /// Check [`Mode`] for more details.
+    ///
+    /// When build datetime is taken from `SOURCE_DATE_EPOCH` environment variable, `cargo:rerun-if-env-changed`
+    /// instruction is emitted for it. Any `rerun-if` instruction disables Cargo's default behaviour of rerunning build
+    /// script whenever a file in the package changes, so build script is rerun only when watched variables or paths
+    /// change. Other values derived from package files by the rest of build script are not updated either.
+    #[must_use]
+    pub fn build<M>(mut self, mode: M) -> Self
+    where
+        M: Into<Mode>,
+    {
+        self.build = mode.into();
+        self
+    }
+
+    /// Sets mode of `cargo` section.
+    ///
+    /// Check [`Mode`] for more details.
+    #[must_use]
+    pub fn cargo<M>(mut self, mode: M) -> Self
+    where
+        M: Into<Mode>,
+    {
+        self.cargo = mode.into();
+        self
+    }
+
+    /// Sets mode of `rust` section.
+    ///
+    /// Check [`Mode`] for more details.
+    #[must_use]
+    pub fn rust<M>(mut self, mode: M) -> Self
+    where
+        M: Into<Mode>,
+    {
+        self.rust = mode.into();
+        self
+    }
+
+    /// Sets mode of `target` section.
+    ///
+    /// Check [`Mode`] for more details.
+    #[must_use]
+    pub fn target<M>(mut self, mode: M) -> Self
+    where
+        M: Into<Mode>,
+    {
+        self.target = mode.into();
+        self
+    }
+
+    /// Sets mode of `git` section.
+    ///
+    /// Section emits `cargo:rerun-if-changed` for `HEAD`, checked out reference, tags, `packed-refs`, index and tracked
+    /// files of repository, so build script isn't rerun when untracked files of the package change.
+    ///
+    /// Check [`Mode`] for more details.
+    #[must_use]
+    pub fn git<M>(mut self, mode: M) -> Self
+    where
+        M: Into<Mode>,
+    {
+        self.git = mode.into();
+        self
+    }
+
+    /// Sets prefix of emitted environment variables.
+    ///
+    /// By default `CHKSUM_BUILD_INFO` prefix is used. The same prefix must be passed to `build_info` macro.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use chksum_build::{BuildScript, Result};
+    ///
+    /// fn main() -> Result<()> {
+    ///     BuildScript::default().prefix("MYAPP").setup()
+    /// }
+    /// ```
+    #[must_use]
+    pub const fn prefix(mut self, prefix: &'static str) -> Self {
+        self.prefix = prefix;
+        self
+    }
+
+    /// Emits bare configuration options like `release` or `nightly` along with namespaced ones.
+    ///
+    /// By default only namespaced configuration options like `chksum_profile = "release"` or
+    /// `chksum_channel = "nightly"` are emitted.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use chksum_build::{BuildScript, Result};
+    ///
+    /// fn main() -> Result<()> {
+    ///     BuildScript::default().bare_cfg(true).setup()
+    /// }
+    /// ```
+    #[must_use]
+    pub const fn bare_cfg(mut self, enabled: bool) -> Self {
+        self.bare_cfg = enabled;
+        self
+    }
+
+    /// Sets range of minor versions of `chksum_rust_1_*` configuration options.
+    ///
+    /// A `chksum_rust_1_N` option is emitted for every minor version `N` from given range up to detected compiler
+    /// version. By default all minor versions are emitted, an empty range disables them.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use chksum_build::{BuildScript, Result};
+    ///
+    /// fn main() -> Result<()> {
+    ///     BuildScript::default().rust_version_cfg(58..=80).setup()
+    /// }
+    /// ```
+    #[must_use]
+    pub fn rust_version_cfg<R>(mut self, minors: R) -> Self
+    where
+        R: RangeBounds<usize>,
+    {
+        self.rust_version_cfg = (minors.start_bound().cloned(), minors.end_bound().cloned());
+        self
+    }
+
+    /// Sets fixed build datetime.
+    ///
+    /// It is used unless `SOURCE_DATE_EPOCH` environment variable is set.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use chksum_build::{BuildScript, Result};
+    /// use chrono::DateTime;
+    ///
+    /// fn main() -> Result<()> {
+    ///     let datetime = DateTime::parse_from_rfc3339("2023-01-02T10:20:30+01:00")?;
+    ///     BuildScript::default().fixed_datetime(datetime).setup()
+    /// }
+    /// ```
+    #[must_use]
+    pub const fn fixed_datetime(mut self, datetime: DateTime<FixedOffset>) -> Self {
+        self.fixed_datetime = Some(datetime);
+        self
+    }
+
+    /// Uses committer datetime of `HEAD` commit as build datetime.
+    ///
+    /// It is used unless `SOURCE_DATE_EPOCH` environment variable is set or fixed datetime is configured. Current
+    /// datetime is used when commit cannot be read.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use chksum_build::{BuildScript, Result};
+    ///
+    /// fn main() -> Result<()> {
+    ///     BuildScript::default().git_commit_datetime(true).setup()
+    /// }
+    /// ```
+    #[must_use]
+    pub const fn git_commit_datetime(mut self, enabled: bool) -> Self {
+        self.git_commit_datetime = enabled;
+        self
+    }
+
+    /// Sets offset of emitted build datetime.
+    ///
+    /// By default build datetime is emitted in UTC.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use chksum_build::{BuildScript, Result};
+    /// use chrono::Local;
+    ///
+    /// fn main() -> Result<()> {
+    ///     BuildScript::default()
+    ///         .offset(*Local::now().offset())
+    ///         .setup()
+    /// }
+    /// ```
+    #[must_use]
+    pub const fn offset(mut self, offset: FixedOffset) -> Self {
+        self.offset = Some(offset);
+        self
+    }
+
+    /// Writes source file with `BUILD_INFO` constant and `build_info` function to `OUT_DIR` directory.
+    ///
+    /// By default `chksum_build_info.rs` file is written, or `chksum_build_info_{PREFIX}.rs` when custom prefix is
+    /// configured, which can be included with `include_build_info` macro. Failure is reported as a `cargo:warning`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use chksum_build::{BuildScript, Result};
+    ///
+    /// fn main() -> Result<()> {
+    ///     BuildScript::default().build_info_file(false).setup()
+    /// }
+    /// ```
+    #[must_use]
+    pub const fn build_info_file(mut self, enabled: bool) -> Self {
+        self.build_info_file = enabled;
+        self
+    }
+
     /// Emits `cargo:*` instructions that set enviroment variables or enable compile-time [`cfg`](https://doc.rust-lang.org/reference/conditional-compilation.html#forms-of-conditional-compilation) settings.
     ///
     /// Resources:
@@ -45,7 +347,7 @@ impl BuildScript {
     ///
     /// # Errors
     ///
-    /// Returns an error when environment variables couldn't be parsed.
+    /// Returns an error when environment variables couldn't be parsed by any of required sections.
     ///
     /// # Examples
     ///
@@ -59,93 +361,1011 @@ impl BuildScript {
     pub fn setup(&self) -> Result<()> {
         let mut stdout_buffer = String::new();
 
-        self.setup_build(&mut stdout_buffer)?;
+        // compiler is checked once and shared by check-cfg declarations and `rust` section
+        let rustc_version = match self.rust {
+            Mode::Disabled => None,
+            Mode::Required | Mode::Optional => Some(Self::rustc_version()),
+        };
+        let rust_version = rustc_version.as_ref().and_then(Self::rust_version);
+
+        self.setup_check_cfg(&mut stdout_buffer, rust_version)?;
+
+        self.setup_section(&mut stdout_buffer, "build", self.build, Self::setup_build)?;
+
+        self.setup_section(&mut stdout_buffer, "cargo", self.cargo, Self::setup_cargo)?;
 
-        self.setup_cargo(&mut stdout_buffer)?;
+        if let Some(rustc_version) = rustc_version {
+            self.setup_section(&mut stdout_buffer, "rust", self.rust, |build_script, stdout| {
+                build_script.setup_rust(stdout, rustc_version)
+            })?;
+        }
 
-        self.setup_rust(&mut stdout_buffer)?;
+        self.setup_section(&mut stdout_buffer, "target", self.target, Self::setup_target)?;
+
+        self.setup_section(&mut stdout_buffer, "git", self.git, Self::setup_git)?;
+
+        if self.build_info_file {
+            if let Err(error) = self.setup_build_info_file(&stdout_buffer) {
+                writeln!(stdout_buffer, "cargo:warning=cannot generate build info file: {error}")?;
+            }
+        }
 
         print!("{stdout_buffer}");
 
         Ok(())
     }
 
-    fn setup_build<T>(&self, stdout: &mut T) -> Result<()>
+    fn setup_section<T, S>(&self, stdout: &mut T, name: &str, mode: Mode, setup: S) -> Result<()>
     where
         T: Write,
+        S: FnOnce(&Self, &mut String) -> Result<()>,
     {
-        let datetime = Local::now().format("%Y-%m-%d %H:%M:%S");
+        let mut section_buffer = String::new();
+
+        match mode {
+            Mode::Disabled => {},
+            Mode::Required => {
+                setup(self, &mut section_buffer)?;
+                stdout.write_str(&section_buffer)?;
+            },
+            Mode::Optional => {
+                match setup(self, &mut section_buffer) {
+                    Ok(()) => stdout.write_str(&section_buffer)?,
+                    Err(error) => writeln!(stdout, "cargo:warning=cannot set up {name} section: {error}")?,
+                }
+            },
+        }
 
+        Ok(())
+    }
+
+    fn setup_build_info_file(&self, stdout: &str) -> Result<()> {
+        let directory = env_var("OUT_DIR")?;
+        let values = Values::parse(stdout, self.prefix);
+        let source = codegen::render(&values, |name| env::var(name).ok())?;
+        let path = Path::new(&directory).join(self.build_info_file_name());
+        fs::write(path, source)?;
+        Ok(())
+    }
+
+    /// Returns name of file written by [`BuildScript::setup_build_info_file`].
+    fn build_info_file_name(&self) -> String {
+        if self.prefix == Self::DEFAULT_PREFIX {
+            String::from("chksum_build_info.rs")
+        } else {
+            format!("chksum_build_info_{}.rs", self.prefix)
+        }
+    }
+
+    fn setup_check_cfg<T>(&self, stdout: &mut T, rust_version: Option<ChannelVersion>) -> Result<()>
+    where
+        T: Write,
+    {
+        let cargo_version = env::var_os("CARGO")
+            .and_then(|cargo| Command::new(cargo).arg("--version").output().ok())
+            .and_then(|output| String::from_utf8(output.stdout).ok())
+            .and_then(|version| CargoVersion::from_str(version.trim()).ok());
+
+        match cargo_version {
+            Some(cargo_version) if cargo_version.supports_check_cfg() => {
+                self.setup_check_cfg_values(stdout, rust_version)
+            },
+            _ => Ok(()),
+        }
+    }
+
+    /// Declares all values of configuration options which can be emitted by enabled sections.
+    ///
+    /// Single colon `cargo:` prefix is used since `cargo::` prefix isn't accepted for packages with `rust-version`
+    /// lower than 1.77.
+    fn setup_check_cfg_values<T>(&self, stdout: &mut T, rust_version: Option<ChannelVersion>) -> Result<()>
+    where
+        T: Write,
+    {
+        if self.cargo != Mode::Disabled {
+            // custom profiles can have any name
+            writeln!(stdout, "cargo:rustc-check-cfg=cfg(chksum_profile, values(any()))")?;
+            if self.bare_cfg {
+                writeln!(stdout, "cargo:rustc-check-cfg=cfg(debug, release)")?;
+            }
+        }
+        if self.rust != Mode::Disabled {
+            let values = [Channel::Beta, Channel::Nightly, Channel::Stable].map(|channel| channel.to_string());
+            self.setup_check_cfg_value(stdout, "channel", &values)?;
+
+            // declare options up to configured maximum even if it is newer than detected compiler
+            let maximum_minor = match self.rust_version_cfg.1 {
+                Bound::Included(minor) => Some(minor),
+                Bound::Excluded(minor) => minor.checked_sub(1),
+                Bound::Unbounded => None,
+            };
+            let maximum_minor = match (maximum_minor, rust_version) {
+                (Some(minor), Some(version)) => Some(minor.max(version.minor())),
+                (minor, None) => minor,
+                (None, Some(version)) => Some(version.minor()),
+            };
+            if let Some(maximum_minor) = maximum_minor {
+                let names = self
+                    .rust_version_minors(maximum_minor)
+                    .map(|minor| format!("chksum_rust_1_{minor}"))
+                    .collect::<Vec<_>>();
+                if !names.is_empty() {
+                    let names = names.join(", ");
+                    writeln!(stdout, "cargo:rustc-check-cfg=cfg({names})")?;
+                }
+            }
+        }
+        if self.target != Mode::Disabled {
+            writeln!(stdout, "cargo:rustc-check-cfg=cfg(chksum_host, values(any()))")?;
+            writeln!(stdout, "cargo:rustc-check-cfg=cfg(chksum_target, values(any()))")?;
+            writeln!(stdout, "cargo:rustc-check-cfg=cfg(chksum_cross_compiled)")?;
+        }
+        Ok(())
+    }
+
+    fn setup_check_cfg_value<T>(&self, stdout: &mut T, name: &str, values: &[String]) -> Result<()>
+    where
+        T: Write,
+    {
+        let quoted_values = values
+            .iter()
+            .map(|value| format!("{value:?}"))
+            .collect::<Vec<_>>()
+            .join(", ");
         writeln!(
             stdout,
-            "cargo:rustc-env=CHKSUM_BUILD_INFO_BUILD_DATETIME=\"{datetime}\""
+            "cargo:rustc-check-cfg=cfg(chksum_{name}, values({quoted_values}))"
         )?;
+        if self.bare_cfg {
+            let names = values.join(", ");
+            writeln!(stdout, "cargo:rustc-check-cfg=cfg({names})")?;
+        }
+        Ok(())
+    }
+
+    fn setup_cfg<T, V>(&self, stdout: &mut T, name: &str, value: V) -> Result<()>
+    where
+        T: Write,
+        V: Display,
+    {
+        writeln!(stdout, "cargo:rustc-cfg=chksum_{name}=\"{value}\"")?;
+        if self.bare_cfg {
+            writeln!(stdout, "cargo:rustc-cfg={value}")?;
+        }
+        Ok(())
+    }
+
+    fn setup_env<T, V>(&self, stdout: &mut T, name: &str, value: V) -> Result<()>
+    where
+        T: Write,
+        V: Display,
+    {
+        let prefix = self.prefix;
+        writeln!(stdout, "cargo:rustc-env={prefix}_{name}={value}")?;
+        Ok(())
+    }
+
+    fn setup_build<T>(&self, stdout: &mut T) -> Result<()>
+    where
+        T: Write,
+    {
+        let source_date_epoch = env::var("SOURCE_DATE_EPOCH").ok();
+        let directory = env_var("CARGO_MANIFEST_DIR")?;
+        let (datetime, source) = self.build_datetime(source_date_epoch.as_deref(), Path::new(&directory))?;
+        if source == DatetimeSource::SourceDateEpoch {
+            writeln!(stdout, "cargo:rerun-if-env-changed=SOURCE_DATE_EPOCH")?;
+        }
+        let datetime = datetime.to_rfc3339_opts(SecondsFormat::Secs, true);
+
+        self.setup_env(stdout, "BUILD_DATETIME", datetime)?;
+        self.setup_env(stdout, "BUILD_DATETIME_SOURCE", source)?;
 
         Ok(())
     }
 
+    /// Returns build datetime along with its source.
+    fn build_datetime(
+        &self,
+        source_date_epoch: Option<&str>,
+        directory: &Path,
+    ) -> Result<(DateTime<FixedOffset>, DatetimeSource)> {
+        let offset = self.offset.unwrap_or_else(|| Utc.fix());
+
+        if let Some(source_date_epoch) = source_date_epoch.filter(|source_date_epoch| !source_date_epoch.is_empty()) {
+            let timestamp = source_date_epoch.parse()?;
+            let datetime = Utc
+                .timestamp_opt(timestamp, 0)
+                .single()
+                .ok_or_else(|| Error::SourceDateEpoch(source_date_epoch.to_string()))?;
+            return Ok((datetime.with_timezone(&offset), DatetimeSource::SourceDateEpoch));
+        }
+
+        if let Some(datetime) = self.fixed_datetime {
+            return Ok((datetime.with_timezone(&offset), DatetimeSource::Fixed));
+        }
+
+        if self.git_commit_datetime {
+            let commit_datetime = Head::read(directory)
+                .ok()
+                .flatten()
+                .and_then(|head| head.commit_datetime);
+            if let Some(datetime) = commit_datetime {
+                return Ok((datetime.with_timezone(&offset), DatetimeSource::GitCommit));
+            }
+        }
+
+        Ok((Utc::now().with_timezone(&offset), DatetimeSource::Now))
+    }
+
     fn setup_cargo<T>(&self, stdout: &mut T) -> Result<()>
     where
         T: Write,
     {
+        self.setup_cargo_values(stdout, |name| env::var(name).ok())?;
+
+        let features = env::var("CARGO_MANIFEST_DIR")
+            .ok()
+            .and_then(|directory| fs::read_to_string(Path::new(&directory).join("Cargo.toml")).ok())
+            .map(|manifest| Features::parse(&manifest))
+            .unwrap_or_default();
+        let variables = env::vars_os().filter_map(|(name, _)| name.into_string().ok());
+        self.setup_cargo_features(stdout, variables, &features)
+    }
+
+    /// Emits sorted, comma-separated list of enabled features.
+    ///
+    /// Names of `CARGO_FEATURE_*` variables are converted back to names used by manifest.
+    fn setup_cargo_features<T, I>(&self, stdout: &mut T, variables: I, features: &Features) -> Result<()>
+    where
+        T: Write,
+        I: IntoIterator<Item = String>,
+    {
+        let mut enabled = variables
+            .into_iter()
+            .filter_map(|variable| {
+                variable
+                    .strip_prefix("CARGO_FEATURE_")
+                    .map(|feature| features.name(feature))
+            })
+            .collect::<Vec<_>>();
+        enabled.sort();
+        enabled.dedup();
+
+        self.setup_env(stdout, "CARGO_FEATURES", enabled.join(","))?;
+
+        Ok(())
+    }
+
+    /// Emits profile and its settings.
+    ///
+    /// Bare configuration option of custom profile is the built-in profile it inherits from.
+    fn setup_cargo_values<T, F>(&self, stdout: &mut T, var: F) -> Result<()>
+    where
+        T: Write,
+        F: Fn(&str) -> Option<String>,
+    {
+        let required = |name: &str| required_var(&var, name);
+
         let profile = {
-            let profile = env::var("PROFILE")?;
-            Profile::from_str(&profile)?
+            let inherits = BaseProfile::from_str(&required("PROFILE")?)?;
+            let out_dir = var("OUT_DIR");
+            Self::profile(inherits, out_dir.as_deref().map(Path::new))
         };
+        let opt_level = required("OPT_LEVEL")?;
+        let debug = required("DEBUG")? == "true";
+        let debug_assertions = var("CARGO_CFG_DEBUG_ASSERTIONS").is_some();
 
-        writeln!(stdout, "cargo:rustc-cfg={profile}")?;
-        writeln!(stdout, "cargo:rustc-env=CHKSUM_BUILD_INFO_CARGO_PROFILE=\"{profile}\"")?;
+        writeln!(stdout, "cargo:rustc-cfg=chksum_profile=\"{}\"", profile.name())?;
+        if self.bare_cfg {
+            writeln!(stdout, "cargo:rustc-cfg={}", profile.inherits())?;
+        }
+        self.setup_env(stdout, "CARGO_PROFILE", &profile)?;
+        self.setup_env(stdout, "CARGO_OPT_LEVEL", opt_level)?;
+        self.setup_env(stdout, "CARGO_DEBUG", debug)?;
+        self.setup_env(stdout, "CARGO_DEBUG_ASSERTIONS", debug_assertions)?;
 
         Ok(())
     }
 
-    fn setup_rust<T>(&self, stdout: &mut T) -> Result<()>
+    /// Returns profile named after directory of `OUT_DIR` path, e.g. `dist` in `target/dist/build/crate-hash/out`.
+    ///
+    /// Falls back to built-in profile when `OUT_DIR` doesn't follow Cargo's layout.
+    fn profile(inherits: BaseProfile, out_dir: Option<&Path>) -> Profile {
+        let name = out_dir.and_then(|out_dir| {
+            let build = out_dir.parent()?.parent()?;
+            if build.file_name()? != "build" {
+                return None;
+            }
+            build.parent()?.file_name()?.to_str()
+        });
+
+        // built-in profiles use `debug` and `release` directories, e.g. `test` profile uses `debug` one
+        match name.filter(|name| BaseProfile::from_str(name).is_err()) {
+            Some(name) => {
+                Profile::Custom {
+                    name: name.to_string().into(),
+                    inherits,
+                }
+            },
+            None if inherits == BaseProfile::Debug => Profile::Debug,
+            None => Profile::Release,
+        }
+    }
+
+    /// Emits compiler information, rustup toolchain is used only when compiler cannot be run.
+    fn setup_rust<T>(&self, stdout: &mut T, rustc_version: Result<RustcVersion>) -> Result<()>
     where
         T: Write,
     {
-        let toolchain = {
-            let toolchain = env::var("RUSTUP_TOOLCHAIN")?;
-            Toolchain::from_str(&toolchain)?
+        match rustc_version {
+            Ok(rustc_version) => self.setup_rustc_version(stdout, &rustc_version),
+            Err(error) => {
+                let (channel, version) = Self::toolchain()?.ok_or(error)?;
+                self.setup_rust_channel(stdout, &channel, version)
+            },
+        }
+    }
+
+    fn setup_rustc_version<T>(&self, stdout: &mut T, rustc_version: &RustcVersion) -> Result<()>
+    where
+        T: Write,
+    {
+        self.setup_rust_channel(stdout, rustc_version.channel(), Some(rustc_version.version()))?;
+
+        if let Some(commit_hash) = rustc_version.commit_hash() {
+            self.setup_env(stdout, "RUST_COMMIT_HASH", commit_hash)?;
+        }
+        if let Some(commit_date) = rustc_version.commit_date() {
+            self.setup_env(stdout, "RUST_COMMIT_DATE", commit_date.format("%Y-%m-%d"))?;
+        }
+        if let Some(llvm_version) = rustc_version.llvm_version() {
+            self.setup_env(stdout, "RUST_LLVM_VERSION", llvm_version)?;
+        }
+
+        Ok(())
+    }
+
+    fn setup_rust_channel<T>(&self, stdout: &mut T, channel: &Channel, version: Option<ChannelVersion>) -> Result<()>
+    where
+        T: Write,
+    {
+        self.setup_cfg(stdout, "channel", channel)?;
+        self.setup_env(stdout, "RUST_CHANNEL", channel)?;
+
+        if let Some(version) = version {
+            self.setup_env(stdout, "RUST_VERSION", version)?;
+            self.setup_rust_version_cfg(stdout, version)?;
+        }
+
+        Ok(())
+    }
+
+    fn setup_rust_version_cfg<T>(&self, stdout: &mut T, version: ChannelVersion) -> Result<()>
+    where
+        T: Write,
+    {
+        if version.major() != 1 {
+            return Ok(());
+        }
+
+        for minor in self.rust_version_minors(version.minor()) {
+            writeln!(stdout, "cargo:rustc-cfg=chksum_rust_1_{minor}")?;
+        }
+
+        Ok(())
+    }
+
+    /// Returns configured minor versions not newer than given one.
+    fn rust_version_minors(&self, maximum_minor: usize) -> impl Iterator<Item = usize> + '_ {
+        (0..=maximum_minor).filter(move |minor| self.rust_version_cfg.contains(minor))
+    }
+
+    /// Returns compiler version, rustup toolchain is used only when compiler cannot be run.
+    fn rust_version(rustc_version: &Result<RustcVersion>) -> Option<ChannelVersion> {
+        match rustc_version {
+            Ok(rustc_version) => Some(rustc_version.version()),
+            Err(_) => Self::toolchain().ok().flatten().and_then(|(_, version)| version),
+        }
+    }
+
+    /// Returns channel and version of rustup toolchain or `None` when toolchain isn't set.
+    fn toolchain() -> Result<Option<(Channel, Option<ChannelVersion>)>> {
+        let toolchain = match env::var("RUSTUP_TOOLCHAIN") {
+            Ok(toolchain) => toolchain,
+            Err(_) => return Ok(None),
         };
-        let channel = match toolchain.channel {
-            Channel::Version(_) => Channel::Stable,
-            channel => channel,
+        let toolchain = Toolchain::from_str(&toolchain)?;
+        let rust = match toolchain.channel() {
+            Channel::Version(version) => (Channel::Stable, Some(*version)),
+            channel => (channel.clone(), None),
         };
 
-        writeln!(stdout, "cargo:rustc-cfg={channel}")?;
-        writeln!(stdout, "cargo:rustc-env=CHKSUM_BUILD_INFO_RUST_CHANNEL=\"{channel}\"")?;
+        Ok(Some(rust))
+    }
+
+    /// Runs `rustc -vV` with compiler passed by Cargo.
+    fn rustc_version() -> Result<RustcVersion> {
+        let rustc = env::var_os("RUSTC").unwrap_or_else(|| "rustc".into());
+        let output = Command::new(rustc).arg("-vV").output()?;
+        if !output.status.success() {
+            let error = format!("cannot check version: {}", output.status);
+            return Err(Error::Rustc(error));
+        }
+        let output = String::from_utf8_lossy(&output.stdout);
+        RustcVersion::from_str(&output)
+    }
+
+    fn setup_target<T>(&self, stdout: &mut T) -> Result<()>
+    where
+        T: Write,
+    {
+        let host = {
+            let host = env_var("HOST")?;
+            Host::from_str(&host)?
+        };
+        let target = {
+            let target = env_var("TARGET")?;
+            Host::from_str(&target)?
+        };
+
+        self.setup_target_hosts(stdout, &host, &target)?;
+        self.setup_target_cfg(stdout, |name| env::var(name).ok())?;
 
         Ok(())
     }
+
+    /// Emits host and target triples.
+    ///
+    /// Bare configuration options aren't emitted since triples aren't valid identifiers.
+    fn setup_target_hosts<T>(&self, stdout: &mut T, host: &Host, target: &Host) -> Result<()>
+    where
+        T: Write,
+    {
+        writeln!(stdout, "cargo:rustc-cfg=chksum_host=\"{host}\"")?;
+        writeln!(stdout, "cargo:rustc-cfg=chksum_target=\"{target}\"")?;
+        if host != target {
+            writeln!(stdout, "cargo:rustc-cfg=chksum_cross_compiled")?;
+        }
+        self.setup_env(stdout, "TARGET_HOST", host)?;
+        self.setup_env(stdout, "TARGET_TRIPLE", target)?;
+
+        Ok(())
+    }
+
+    /// Emits target configuration read from `CARGO_CFG_TARGET_*` and `CARGO_CFG_PANIC` variables.
+    ///
+    /// Empty and missing optional variables are skipped.
+    ///
+    /// Resources:
+    /// * [The Cargo Book: Environment variables Cargo sets for build scripts](https://doc.rust-lang.org/cargo/reference/environment-variables.html#environment-variables-cargo-sets-for-build-scripts).
+    fn setup_target_cfg<T, F>(&self, stdout: &mut T, var: F) -> Result<()>
+    where
+        T: Write,
+        F: Fn(&str) -> Option<String>,
+    {
+        let required = |name: &str| required_var(&var, name);
+
+        let os = required("CARGO_CFG_TARGET_OS")?;
+        let arch = required("CARGO_CFG_TARGET_ARCH")?;
+        let vendor = required("CARGO_CFG_TARGET_VENDOR")?;
+        let endian = {
+            let endian = required("CARGO_CFG_TARGET_ENDIAN")?;
+            Endian::from_str(&endian)?
+        };
+        let pointer_width = required("CARGO_CFG_TARGET_POINTER_WIDTH")?.parse::<u32>()?;
+
+        self.setup_env(stdout, "TARGET_CFG_OS", os)?;
+        self.setup_env(stdout, "TARGET_CFG_ARCH", arch)?;
+        self.setup_env(stdout, "TARGET_CFG_VENDOR", vendor)?;
+        self.setup_env(stdout, "TARGET_CFG_ENDIAN", endian)?;
+        self.setup_env(stdout, "TARGET_CFG_POINTER_WIDTH", pointer_width)?;
+
+        let optional = [
+            ("TARGET_CFG_ENV", "CARGO_CFG_TARGET_ENV"),
+            ("TARGET_CFG_FAMILY", "CARGO_CFG_TARGET_FAMILY"),
+            ("TARGET_CFG_HAS_ATOMIC", "CARGO_CFG_TARGET_HAS_ATOMIC"),
+            ("TARGET_CFG_FEATURE", "CARGO_CFG_TARGET_FEATURE"),
+        ];
+        for (name, variable) in optional {
+            if let Some(value) = var(variable).filter(|value| !value.is_empty()) {
+                self.setup_env(stdout, name, value)?;
+            }
+        }
+
+        if let Some(panic) = var("CARGO_CFG_PANIC") {
+            let panic = Panic::from_str(&panic)?;
+            self.setup_env(stdout, "TARGET_CFG_PANIC", panic)?;
+        }
+
+        Ok(())
+    }
+
+    fn setup_git<T>(&self, stdout: &mut T) -> Result<()>
+    where
+        T: Write,
+    {
+        let directory = env_var("CARGO_MANIFEST_DIR")?;
+        self.setup_git_repository(stdout, Path::new(&directory))
+    }
+
+    fn setup_git_repository<T>(&self, stdout: &mut T, directory: &Path) -> Result<()>
+    where
+        T: Write,
+    {
+        let head = match Head::read(directory)? {
+            Some(head) => head,
+            None => return Ok(()),
+        };
+        let Head {
+            commit_hash,
+            commit_datetime,
+            dirty,
+            branch,
+            tag,
+            path_in_vcs,
+            watched_paths,
+        } = head;
+
+        let commit_short_hash = &commit_hash[..Self::GIT_SHORT_HASH_LENGTH.min(commit_hash.len())];
+
+        self.setup_env(stdout, "GIT_COMMIT_HASH", &commit_hash)?;
+        self.setup_env(stdout, "GIT_COMMIT_SHORT_HASH", commit_short_hash)?;
+        self.setup_env(stdout, "GIT_DIRTY", dirty)?;
+        if let Some(commit_datetime) = commit_datetime {
+            let commit_datetime = commit_datetime.to_rfc3339();
+            self.setup_env(stdout, "GIT_COMMIT_DATETIME", commit_datetime)?;
+        }
+        if let Some(branch) = branch {
+            self.setup_env(stdout, "GIT_BRANCH", branch)?;
+        }
+        if let Some(tag) = tag {
+            self.setup_env(stdout, "GIT_TAG", tag)?;
+        }
+        if let Some(path_in_vcs) = path_in_vcs {
+            self.setup_env(stdout, "GIT_PATH_IN_VCS", path_in_vcs)?;
+        }
+        for path in watched_paths {
+            writeln!(stdout, "cargo:rerun-if-changed={}", path.display())?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Returns value of required environment variable of current process.
+fn env_var(name: &str) -> Result<String> {
+    required_var(|name| env::var(name).ok(), name)
+}
+
+/// Returns value of required variable, fails with [`Error::MissingEnvVar`] when it is not set.
+fn required_var<F>(var: F, name: &str) -> Result<String>
+where
+    F: Fn(&str) -> Option<String>,
+{
+    var(name).ok_or_else(|| Error::MissingEnvVar(name.to_string()))
 }
 
 #[cfg(test)]
 mod tests {
+    use std::process::Command;
+
     use super::*;
 
+    #[test]
+    fn test_build_script_builder() {
+        let build_script = BuildScript::new();
+        assert_eq!(build_script.build, Mode::Required);
+        assert_eq!(build_script.cargo, Mode::Required);
+        assert_eq!(build_script.rust, Mode::Required);
+        assert_eq!(build_script.target, Mode::Required);
+        assert_eq!(build_script.git, Mode::Optional);
+        assert_eq!(build_script.prefix, "CHKSUM_BUILD_INFO");
+        assert!(build_script.build_info_file);
+
+        let build_script = BuildScript::default()
+            .build(false)
+            .cargo(Mode::Optional)
+            .rust(true)
+            .target(Mode::Disabled)
+            .git(true)
+            .prefix("MYAPP")
+            .bare_cfg(true)
+            .build_info_file(false);
+        assert_eq!(build_script.build, Mode::Disabled);
+        assert_eq!(build_script.cargo, Mode::Optional);
+        assert_eq!(build_script.rust, Mode::Required);
+        assert_eq!(build_script.target, Mode::Disabled);
+        assert_eq!(build_script.git, Mode::Required);
+        assert_eq!(build_script.prefix, "MYAPP");
+        assert!(build_script.bare_cfg);
+        assert!(!build_script.build_info_file);
+    }
+
+    #[test]
+    fn test_setup_section() -> Result<()> {
+        fn ok(_: &BuildScript, stdout: &mut String) -> Result<()> {
+            writeln!(stdout, "cargo:rustc-cfg=ok")?;
+            Ok(())
+        }
+
+        fn error(_: &BuildScript, stdout: &mut String) -> Result<()> {
+            writeln!(stdout, "cargo:rustc-cfg=error")?;
+            Err(Error::Git(String::from("error")))
+        }
+
+        let build_script = BuildScript::new();
+        let mut stdout = String::new();
+        build_script.setup_section(&mut stdout, "ok", Mode::Disabled, ok)?;
+        build_script.setup_section(&mut stdout, "error", Mode::Disabled, error)?;
+        assert_eq!(stdout, "");
+
+        build_script.setup_section(&mut stdout, "ok", Mode::Required, ok)?;
+        assert!(build_script
+            .setup_section(&mut stdout, "error", Mode::Required, error)
+            .is_err());
+        assert_eq!(stdout, "cargo:rustc-cfg=ok\n");
+
+        let mut stdout = String::new();
+        build_script.setup_section(&mut stdout, "ok", Mode::Optional, ok)?;
+        build_script.setup_section(&mut stdout, "error", Mode::Optional, error)?;
+        assert_eq!(
+            stdout,
+            "cargo:rustc-cfg=ok\ncargo:warning=cannot set up error section: git: error\n"
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_setup_check_cfg_values() -> Result<()> {
+        let mut stdout = String::new();
+        BuildScript::default()
+            .rust_version_cfg(2..)
+            .setup_check_cfg_values(&mut stdout, Some(ChannelVersion::MajorMinorPatch(1, 4, 0)))?;
+        assert_eq!(
+            stdout.lines().collect::<Vec<_>>(),
+            vec![
+                "cargo:rustc-check-cfg=cfg(chksum_profile, values(any()))",
+                "cargo:rustc-check-cfg=cfg(chksum_channel, values(\"beta\", \"nightly\", \"stable\"))",
+                "cargo:rustc-check-cfg=cfg(chksum_rust_1_2, chksum_rust_1_3, chksum_rust_1_4)",
+                "cargo:rustc-check-cfg=cfg(chksum_host, values(any()))",
+                "cargo:rustc-check-cfg=cfg(chksum_target, values(any()))",
+                "cargo:rustc-check-cfg=cfg(chksum_cross_compiled)",
+            ]
+        );
+
+        let mut stdout = String::new();
+        BuildScript::default()
+            .rust_version_cfg(3..6)
+            .target(false)
+            .setup_check_cfg_values(&mut stdout, None)?;
+        assert_eq!(
+            stdout.lines().last(),
+            Some("cargo:rustc-check-cfg=cfg(chksum_rust_1_3, chksum_rust_1_4, chksum_rust_1_5)")
+        );
+
+        let mut stdout = String::new();
+        BuildScript::default()
+            .rust(false)
+            .target(false)
+            .bare_cfg(true)
+            .setup_check_cfg_values(&mut stdout, None)?;
+        assert_eq!(
+            stdout.lines().collect::<Vec<_>>(),
+            vec![
+                "cargo:rustc-check-cfg=cfg(chksum_profile, values(any()))",
+                "cargo:rustc-check-cfg=cfg(debug, release)",
+            ]
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_setup_rust_version_cfg() -> Result<()> {
+        let version = ChannelVersion::MajorMinorPatch(1, 3, 1);
+
+        let mut stdout = String::new();
+        BuildScript::default().setup_rust_version_cfg(&mut stdout, version)?;
+        assert_eq!(
+            stdout.lines().collect::<Vec<_>>(),
+            vec![
+                "cargo:rustc-cfg=chksum_rust_1_0",
+                "cargo:rustc-cfg=chksum_rust_1_1",
+                "cargo:rustc-cfg=chksum_rust_1_2",
+                "cargo:rustc-cfg=chksum_rust_1_3",
+            ]
+        );
+
+        let mut stdout = String::new();
+        BuildScript::default()
+            .rust_version_cfg(2..=10)
+            .setup_rust_version_cfg(&mut stdout, version)?;
+        assert_eq!(
+            stdout.lines().collect::<Vec<_>>(),
+            vec!["cargo:rustc-cfg=chksum_rust_1_2", "cargo:rustc-cfg=chksum_rust_1_3"]
+        );
+
+        let mut stdout = String::new();
+        BuildScript::default()
+            .rust_version_cfg(..0)
+            .setup_rust_version_cfg(&mut stdout, version)?;
+        assert_eq!(stdout, "");
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_setup_cfg() -> Result<()> {
+        let mut stdout = String::new();
+        BuildScript::default().setup_cfg(&mut stdout, "profile", "release")?;
+        BuildScript::default()
+            .bare_cfg(true)
+            .setup_cfg(&mut stdout, "channel", "nightly")?;
+        assert_eq!(
+            stdout.lines().collect::<Vec<_>>(),
+            vec![
+                "cargo:rustc-cfg=chksum_profile=\"release\"",
+                "cargo:rustc-cfg=chksum_channel=\"nightly\"",
+                "cargo:rustc-cfg=nightly",
+            ]
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_setup_env() -> Result<()> {
+        let mut stdout = String::new();
+        BuildScript::default().setup_env(&mut stdout, "CARGO_PROFILE", "release")?;
+        BuildScript::default()
+            .prefix("MYAPP")
+            .setup_env(&mut stdout, "CARGO_PROFILE", "debug")?;
+        assert_eq!(
+            stdout.lines().collect::<Vec<_>>(),
+            vec![
+                "cargo:rustc-env=CHKSUM_BUILD_INFO_CARGO_PROFILE=release",
+                "cargo:rustc-env=MYAPP_CARGO_PROFILE=debug",
+            ]
+        );
+        Ok(())
+    }
+
     #[test]
     fn test_setup_build() {
         let mut stdout = String::new();
         assert!(BuildScript::default().setup_build(&mut stdout).is_ok());
+        let lines = stdout.lines().collect::<Vec<_>>();
+        assert_eq!(lines.len(), 2);
+        let datetime = lines[0]
+            .strip_prefix("cargo:rustc-env=CHKSUM_BUILD_INFO_BUILD_DATETIME=")
+            .unwrap();
+        let datetime = DateTime::parse_from_rfc3339(datetime).unwrap();
+        assert_eq!(datetime.offset(), &Utc.fix());
+        assert!(Utc::now().signed_duration_since(datetime).num_seconds() < 60);
+        assert_eq!(lines[1], "cargo:rustc-env=CHKSUM_BUILD_INFO_BUILD_DATETIME_SOURCE=now");
+    }
+
+    #[test]
+    fn test_build_datetime() -> Result<()> {
+        let directory = tempfile::tempdir()?;
+        let datetime = DateTime::parse_from_rfc3339;
+        let fixed_datetime = datetime("2022-11-27T01:02:03+02:00")?;
+        let offset = FixedOffset::east_opt(3600).unwrap();
+
+        assert_eq!(
+            BuildScript::default().build_datetime(Some("1672651230"), directory.path())?,
+            (datetime("2023-01-02T09:20:30Z")?, DatetimeSource::SourceDateEpoch)
+        );
+        assert_eq!(
+            BuildScript::default()
+                .fixed_datetime(fixed_datetime)
+                .build_datetime(Some("1672651230"), directory.path())?,
+            (datetime("2023-01-02T09:20:30Z")?, DatetimeSource::SourceDateEpoch)
+        );
+        assert!(BuildScript::default()
+            .build_datetime(Some("yesterday"), directory.path())
+            .is_err());
+        let (fixed, source) = BuildScript::default()
+            .fixed_datetime(fixed_datetime)
+            .git_commit_datetime(true)
+            .offset(offset)
+            .build_datetime(Some(""), directory.path())?;
+        assert_eq!(fixed.to_rfc3339(), "2022-11-27T00:02:03+01:00");
+        assert_eq!(source, DatetimeSource::Fixed);
+        assert_eq!(
+            BuildScript::default()
+                .git_commit_datetime(true)
+                .build_datetime(None, directory.path())?
+                .1,
+            DatetimeSource::Now
+        );
+
+        let git = |args: &[&str]| {
+            Command::new("git")
+                .args(args)
+                .current_dir(directory.path())
+                .env("GIT_AUTHOR_NAME", "chksum")
+                .env("GIT_AUTHOR_EMAIL", "chksum@example.com")
+                .env("GIT_COMMITTER_NAME", "chksum")
+                .env("GIT_COMMITTER_EMAIL", "chksum@example.com")
+                .env("GIT_COMMITTER_DATE", "2023-01-02T10:20:30+01:00")
+                .output()
+        };
+        git(&["init", "--quiet"])?;
+        git(&["commit", "--quiet", "--allow-empty", "--message", "initial"])?;
+
+        assert_eq!(
+            BuildScript::default()
+                .git_commit_datetime(true)
+                .build_datetime(None, directory.path())?,
+            (datetime("2023-01-02T09:20:30Z")?, DatetimeSource::GitCommit)
+        );
         assert_eq!(
-            stdout.to_string(),
-            format!(
-                "cargo:rustc-env=CHKSUM_BUILD_INFO_BUILD_DATETIME=\"{}\"\n",
-                Local::now().format("%Y-%m-%d %H:%M:%S")
-            )
+            BuildScript::default().build_datetime(None, directory.path())?.1,
+            DatetimeSource::Now
         );
+
+        Ok(())
     }
 
     #[test]
     fn test_setup_cargo() {
         env::set_var("PROFILE", "release");
+        env::set_var("OPT_LEVEL", "3");
+        env::set_var("DEBUG", "false");
 
         let mut stdout = String::new();
         assert!(BuildScript::default().setup_cargo(&mut stdout).is_ok());
+        assert!(stdout.starts_with(
+            "cargo:rustc-cfg=chksum_profile=\"release\"\ncargo:rustc-env=CHKSUM_BUILD_INFO_CARGO_PROFILE=release\n"
+        ));
+    }
+
+    #[test]
+    fn test_setup_cargo_values() -> Result<()> {
+        let var = |name: &str| {
+            match name {
+                "PROFILE" => Some(String::from("release")),
+                "OPT_LEVEL" => Some(String::from("3")),
+                "DEBUG" => Some(String::from("false")),
+                _ => None,
+            }
+        };
+
+        let mut stdout = String::new();
+        BuildScript::default().setup_cargo_values(&mut stdout, var)?;
         assert_eq!(
-            stdout.to_string(),
-            "cargo:rustc-cfg=release\ncargo:rustc-env=CHKSUM_BUILD_INFO_CARGO_PROFILE=\"release\"\n"
+            stdout.lines().collect::<Vec<_>>(),
+            vec![
+                "cargo:rustc-cfg=chksum_profile=\"release\"",
+                "cargo:rustc-env=CHKSUM_BUILD_INFO_CARGO_PROFILE=release",
+                "cargo:rustc-env=CHKSUM_BUILD_INFO_CARGO_OPT_LEVEL=3",
+                "cargo:rustc-env=CHKSUM_BUILD_INFO_CARGO_DEBUG=false",
+                "cargo:rustc-env=CHKSUM_BUILD_INFO_CARGO_DEBUG_ASSERTIONS=false",
+            ]
         );
+
+        let var = |name: &str| {
+            match name {
+                "OUT_DIR" => Some(String::from("/crate/target/dist/build/crate-0123456789abcdef/out")),
+                "CARGO_CFG_DEBUG_ASSERTIONS" => Some(String::new()),
+                "DEBUG" => Some(String::from("true")),
+                name => var(name),
+            }
+        };
+        let mut stdout = String::new();
+        BuildScript::default()
+            .bare_cfg(true)
+            .setup_cargo_values(&mut stdout, var)?;
+        assert_eq!(
+            stdout.lines().collect::<Vec<_>>(),
+            vec![
+                "cargo:rustc-cfg=chksum_profile=\"dist\"",
+                "cargo:rustc-cfg=release",
+                "cargo:rustc-env=CHKSUM_BUILD_INFO_CARGO_PROFILE=dist:release",
+                "cargo:rustc-env=CHKSUM_BUILD_INFO_CARGO_OPT_LEVEL=3",
+                "cargo:rustc-env=CHKSUM_BUILD_INFO_CARGO_DEBUG=true",
+                "cargo:rustc-env=CHKSUM_BUILD_INFO_CARGO_DEBUG_ASSERTIONS=true",
+            ]
+        );
+
+        let var = |name: &str| {
+            match name {
+                "PROFILE" => Some(String::from("dist")),
+                name => var(name),
+            }
+        };
+        assert!(BuildScript::default()
+            .setup_cargo_values(&mut String::new(), var)
+            .is_err());
+
+        let var = |name: &str| {
+            match name {
+                "PROFILE" => Some(String::from("release")),
+                "OPT_LEVEL" => None,
+                name => var(name),
+            }
+        };
+        let error = BuildScript::default()
+            .setup_cargo_values(&mut String::new(), var)
+            .unwrap_err();
+        assert_eq!(error.to_string(), "missing OPT_LEVEL environment variable");
+        Ok(())
+    }
+
+    #[test]
+    fn test_setup_cargo_features() -> Result<()> {
+        let features = Features::parse(
+            r#"
+[features]
+default = ["std"]
+std = []
+"async-io" = []
+"#,
+        );
+        let variables = [
+            "CARGO_FEATURE_STD",
+            "PROFILE",
+            "CARGO_FEATURE_ASYNC_IO",
+            "CARGO_FEATURE_DEFAULT",
+        ];
+
+        let mut stdout = String::new();
+        BuildScript::default().setup_cargo_features(&mut stdout, variables.map(String::from), &features)?;
+        assert_eq!(
+            stdout,
+            "cargo:rustc-env=CHKSUM_BUILD_INFO_CARGO_FEATURES=async-io,default,std\n"
+        );
+
+        let features = Features::parse(
+            r#"
+[features]
+async_io = []
+"#,
+        );
+        let mut stdout = String::new();
+        BuildScript::default().setup_cargo_features(
+            &mut stdout,
+            [String::from("CARGO_FEATURE_ASYNC_IO")],
+            &features,
+        )?;
+        assert_eq!(stdout, "cargo:rustc-env=CHKSUM_BUILD_INFO_CARGO_FEATURES=async_io\n");
+
+        let mut stdout = String::new();
+        BuildScript::default().setup_cargo_features(&mut stdout, Vec::new(), &features)?;
+        assert_eq!(stdout, "cargo:rustc-env=CHKSUM_BUILD_INFO_CARGO_FEATURES=\n");
+        Ok(())
+    }
+
+    #[test]
+    fn test_profile() {
+        let profile = |inherits, out_dir: &str| BuildScript::profile(inherits, Some(Path::new(out_dir)));
+
+        assert_eq!(
+            profile(
+                BaseProfile::Debug,
+                "/crate/target/debug/build/crate-0123456789abcdef/out"
+            ),
+            Profile::Debug
+        );
+        assert_eq!(
+            profile(
+                BaseProfile::Release,
+                "/crate/target/x86_64-unknown-linux-gnu/release/build/crate-0123456789abcdef/out"
+            ),
+            Profile::Release
+        );
+        assert_eq!(
+            profile(
+                BaseProfile::Release,
+                "/crate/target/bench-lto/build/crate-0123456789abcdef/out"
+            ),
+            Profile::Custom {
+                name: "bench-lto".into(),
+                inherits: BaseProfile::Release,
+            }
+        );
+        assert_eq!(profile(BaseProfile::Debug, "/tmp/out"), Profile::Debug);
+        assert_eq!(BuildScript::profile(BaseProfile::Release, None), Profile::Release);
     }
 
     #[test]
@@ -153,10 +1373,272 @@ mod tests {
         env::set_var("RUSTUP_TOOLCHAIN", "nightly-x86_64-unknown-linux-gnu");
 
         let mut stdout = String::new();
-        assert!(BuildScript::default().setup_rust(&mut stdout).is_ok());
+        assert!(BuildScript::default()
+            .setup_rust(&mut stdout, BuildScript::rustc_version())
+            .is_ok());
+        let channel = match BuildScript::rustc_version() {
+            Ok(rustc_version) => rustc_version.channel().clone(),
+            Err(_) => Channel::Nightly,
+        };
+        assert!(stdout.starts_with(&format!(
+            "cargo:rustc-cfg=chksum_channel=\"{channel}\"\ncargo:rustc-env=CHKSUM_BUILD_INFO_RUST_CHANNEL={channel}\n"
+        )));
+
+        let error = || Err(Error::Rustc(String::from("cannot check version")));
+        let mut stdout = String::new();
+        assert!(BuildScript::default().setup_rust(&mut stdout, error()).is_ok());
+        assert_eq!(
+            stdout.lines().collect::<Vec<_>>(),
+            vec![
+                "cargo:rustc-cfg=chksum_channel=\"nightly\"",
+                "cargo:rustc-env=CHKSUM_BUILD_INFO_RUST_CHANNEL=nightly",
+            ]
+        );
+        assert_eq!(BuildScript::rust_version(&error()), None);
+    }
+
+    #[test]
+    fn test_setup_rustc_version() -> Result<()> {
+        let rustc_version = RustcVersion::from_str(
+            "rustc 1.74.1 (a28077b28 2023-12-04)
+binary: rustc
+commit-hash: a28077b28a02b92985b3a3faecf92813155f1ea1
+commit-date: 2023-12-04
+host: x86_64-unknown-linux-gnu
+release: 1.74.1
+LLVM version: 17.0.4
+",
+        )?;
+
+        let mut stdout = String::new();
+        BuildScript::default()
+            .rust_version_cfg(..0)
+            .setup_rustc_version(&mut stdout, &rustc_version)?;
+        assert_eq!(
+            stdout.lines().collect::<Vec<_>>(),
+            vec![
+                "cargo:rustc-cfg=chksum_channel=\"stable\"",
+                "cargo:rustc-env=CHKSUM_BUILD_INFO_RUST_CHANNEL=stable",
+                "cargo:rustc-env=CHKSUM_BUILD_INFO_RUST_VERSION=1.74.1",
+                "cargo:rustc-env=CHKSUM_BUILD_INFO_RUST_COMMIT_HASH=a28077b28a02b92985b3a3faecf92813155f1ea1",
+                "cargo:rustc-env=CHKSUM_BUILD_INFO_RUST_COMMIT_DATE=2023-12-04",
+                "cargo:rustc-env=CHKSUM_BUILD_INFO_RUST_LLVM_VERSION=17.0.4",
+            ]
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_setup_rust_channel() -> Result<()> {
+        let mut stdout = String::new();
+        BuildScript::default()
+            .bare_cfg(true)
+            .setup_rust_channel(&mut stdout, &Channel::Nightly, None)?;
+        assert_eq!(
+            stdout.lines().collect::<Vec<_>>(),
+            vec![
+                "cargo:rustc-cfg=chksum_channel=\"nightly\"",
+                "cargo:rustc-cfg=nightly",
+                "cargo:rustc-env=CHKSUM_BUILD_INFO_RUST_CHANNEL=nightly",
+            ]
+        );
+
+        let mut stdout = String::new();
+        BuildScript::default().rust_version_cfg(1..).setup_rust_channel(
+            &mut stdout,
+            &Channel::Stable,
+            Some(ChannelVersion::MajorMinorPatch(1, 2, 0)),
+        )?;
         assert_eq!(
-            stdout.to_string(),
-            "cargo:rustc-cfg=nightly\ncargo:rustc-env=CHKSUM_BUILD_INFO_RUST_CHANNEL=\"nightly\"\n"
+            stdout.lines().collect::<Vec<_>>(),
+            vec![
+                "cargo:rustc-cfg=chksum_channel=\"stable\"",
+                "cargo:rustc-env=CHKSUM_BUILD_INFO_RUST_CHANNEL=stable",
+                "cargo:rustc-env=CHKSUM_BUILD_INFO_RUST_VERSION=1.2.0",
+                "cargo:rustc-cfg=chksum_rust_1_1",
+                "cargo:rustc-cfg=chksum_rust_1_2",
+            ]
         );
+        Ok(())
+    }
+
+    #[test]
+    fn test_setup_target_hosts() -> Result<()> {
+        let host = Host::from_str("x86_64-unknown-linux-gnu")?;
+        let target = Host::from_str("aarch64-unknown-linux-musl")?;
+
+        let mut stdout = String::new();
+        BuildScript::default().setup_target_hosts(&mut stdout, &host, &host)?;
+        assert_eq!(
+            stdout.lines().collect::<Vec<_>>(),
+            vec![
+                "cargo:rustc-cfg=chksum_host=\"x86_64-unknown-linux-gnu\"",
+                "cargo:rustc-cfg=chksum_target=\"x86_64-unknown-linux-gnu\"",
+                "cargo:rustc-env=CHKSUM_BUILD_INFO_TARGET_HOST=x86_64-unknown-linux-gnu",
+                "cargo:rustc-env=CHKSUM_BUILD_INFO_TARGET_TRIPLE=x86_64-unknown-linux-gnu",
+            ]
+        );
+
+        let mut stdout = String::new();
+        BuildScript::default()
+            .bare_cfg(true)
+            .setup_target_hosts(&mut stdout, &host, &target)?;
+        assert_eq!(
+            stdout.lines().collect::<Vec<_>>(),
+            vec![
+                "cargo:rustc-cfg=chksum_host=\"x86_64-unknown-linux-gnu\"",
+                "cargo:rustc-cfg=chksum_target=\"aarch64-unknown-linux-musl\"",
+                "cargo:rustc-cfg=chksum_cross_compiled",
+                "cargo:rustc-env=CHKSUM_BUILD_INFO_TARGET_HOST=x86_64-unknown-linux-gnu",
+                "cargo:rustc-env=CHKSUM_BUILD_INFO_TARGET_TRIPLE=aarch64-unknown-linux-musl",
+            ]
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_setup_target_cfg() -> Result<()> {
+        let variables = [
+            ("CARGO_CFG_TARGET_OS", "linux"),
+            ("CARGO_CFG_TARGET_ARCH", "x86_64"),
+            ("CARGO_CFG_TARGET_VENDOR", "unknown"),
+            ("CARGO_CFG_TARGET_ENDIAN", "little"),
+            ("CARGO_CFG_TARGET_POINTER_WIDTH", "64"),
+            ("CARGO_CFG_TARGET_ENV", "gnu"),
+            ("CARGO_CFG_TARGET_FAMILY", "unix"),
+            ("CARGO_CFG_TARGET_HAS_ATOMIC", "16,32,64,8,ptr"),
+            ("CARGO_CFG_TARGET_FEATURE", "fxsr,sse,sse2"),
+            ("CARGO_CFG_PANIC", "unwind"),
+        ];
+        let var = |name: &str| {
+            variables
+                .iter()
+                .find(|(variable, _)| *variable == name)
+                .map(|(_, value)| value.to_string())
+        };
+
+        let mut stdout = String::new();
+        BuildScript::default().setup_target_cfg(&mut stdout, var)?;
+        assert_eq!(
+            stdout.lines().collect::<Vec<_>>(),
+            vec![
+                "cargo:rustc-env=CHKSUM_BUILD_INFO_TARGET_CFG_OS=linux",
+                "cargo:rustc-env=CHKSUM_BUILD_INFO_TARGET_CFG_ARCH=x86_64",
+                "cargo:rustc-env=CHKSUM_BUILD_INFO_TARGET_CFG_VENDOR=unknown",
+                "cargo:rustc-env=CHKSUM_BUILD_INFO_TARGET_CFG_ENDIAN=little",
+                "cargo:rustc-env=CHKSUM_BUILD_INFO_TARGET_CFG_POINTER_WIDTH=64",
+                "cargo:rustc-env=CHKSUM_BUILD_INFO_TARGET_CFG_ENV=gnu",
+                "cargo:rustc-env=CHKSUM_BUILD_INFO_TARGET_CFG_FAMILY=unix",
+                "cargo:rustc-env=CHKSUM_BUILD_INFO_TARGET_CFG_HAS_ATOMIC=16,32,64,8,ptr",
+                "cargo:rustc-env=CHKSUM_BUILD_INFO_TARGET_CFG_FEATURE=fxsr,sse,sse2",
+                "cargo:rustc-env=CHKSUM_BUILD_INFO_TARGET_CFG_PANIC=unwind",
+            ]
+        );
+
+        let var = |name: &str| {
+            match name {
+                "CARGO_CFG_TARGET_ENV" | "CARGO_CFG_TARGET_FAMILY" => Some(String::new()),
+                "CARGO_CFG_TARGET_HAS_ATOMIC" | "CARGO_CFG_TARGET_FEATURE" | "CARGO_CFG_PANIC" => None,
+                name => var(name),
+            }
+        };
+        let mut stdout = String::new();
+        BuildScript::default().setup_target_cfg(&mut stdout, var)?;
+        assert_eq!(stdout.lines().count(), 5);
+
+        let var = |name: &str| {
+            match name {
+                "CARGO_CFG_TARGET_ENDIAN" => Some(String::from("middle")),
+                name => var(name),
+            }
+        };
+        assert!(BuildScript::default()
+            .setup_target_cfg(&mut String::new(), var)
+            .is_err());
+
+        let var = |name: &str| {
+            match name {
+                "CARGO_CFG_TARGET_OS" => None,
+                name => var(name),
+            }
+        };
+        let error = BuildScript::default()
+            .setup_target_cfg(&mut String::new(), var)
+            .unwrap_err();
+        assert_eq!(error.to_string(), "missing CARGO_CFG_TARGET_OS environment variable");
+        Ok(())
+    }
+
+    #[test]
+    fn test_required_var() {
+        let var = |name: &str| (name == "HOST").then(|| String::from("x86_64-unknown-linux-gnu"));
+        assert_eq!(required_var(var, "HOST").unwrap(), "x86_64-unknown-linux-gnu");
+        let error = required_var(var, "TARGET").unwrap_err();
+        assert_eq!(error.to_string(), "missing TARGET environment variable");
+    }
+
+    #[test]
+    fn test_setup_git() -> Result<()> {
+        let directory = tempfile::tempdir()?;
+        let git = |args: &[&str]| {
+            Command::new("git")
+                .args(args)
+                .current_dir(directory.path())
+                .env("GIT_AUTHOR_NAME", "chksum")
+                .env("GIT_AUTHOR_EMAIL", "chksum@example.com")
+                .env("GIT_COMMITTER_NAME", "chksum")
+                .env("GIT_COMMITTER_EMAIL", "chksum@example.com")
+                .env("GIT_COMMITTER_DATE", "2023-01-02T10:20:30+01:00")
+                .output()
+        };
+
+        let mut stdout = String::new();
+        BuildScript::default().setup_git_repository(&mut stdout, directory.path())?;
+        assert_eq!(stdout, "");
+
+        git(&["init", "--quiet"])?;
+        git(&["symbolic-ref", "HEAD", "refs/heads/main"])?;
+        fs::write(directory.path().join("file.txt"), "first\n")?;
+        git(&["add", "file.txt"])?;
+        git(&["commit", "--quiet", "--message", "initial"])?;
+        git(&["tag", "v0.1.0"])?;
+        let commit_hash = String::from_utf8(git(&["rev-parse", "HEAD"])?.stdout).unwrap();
+        let commit_hash = commit_hash.trim();
+
+        let git_directory = directory.path().join(".git");
+        let mut stdout = String::new();
+        BuildScript::default().setup_git_repository(&mut stdout, directory.path())?;
+        assert_eq!(
+            stdout.lines().collect::<Vec<_>>(),
+            [
+                format!("cargo:rustc-env=CHKSUM_BUILD_INFO_GIT_COMMIT_HASH={commit_hash}"),
+                format!(
+                    "cargo:rustc-env=CHKSUM_BUILD_INFO_GIT_COMMIT_SHORT_HASH={}",
+                    &commit_hash[..7]
+                ),
+                String::from("cargo:rustc-env=CHKSUM_BUILD_INFO_GIT_DIRTY=false"),
+                String::from("cargo:rustc-env=CHKSUM_BUILD_INFO_GIT_COMMIT_DATETIME=2023-01-02T10:20:30+01:00"),
+                String::from("cargo:rustc-env=CHKSUM_BUILD_INFO_GIT_BRANCH=main"),
+                String::from("cargo:rustc-env=CHKSUM_BUILD_INFO_GIT_TAG=v0.1.0"),
+                format!("cargo:rerun-if-changed={}", git_directory.join("HEAD").display()),
+                format!(
+                    "cargo:rerun-if-changed={}",
+                    git_directory.join("refs/heads/main").display()
+                ),
+                format!("cargo:rerun-if-changed={}", git_directory.join("refs/tags").display()),
+                format!("cargo:rerun-if-changed={}", git_directory.join("index").display()),
+                format!("cargo:rerun-if-changed={}", directory.path().join("file.txt").display()),
+            ]
+        );
+
+        // edit of tracked file reruns build script, which reports repository as dirty
+        fs::write(directory.path().join("file.txt"), "first\nsecond\n")?;
+        let mut stdout = String::new();
+        BuildScript::default().setup_git_repository(&mut stdout, directory.path())?;
+        assert!(stdout
+            .lines()
+            .any(|line| line == "cargo:rustc-env=CHKSUM_BUILD_INFO_GIT_DIRTY=true"));
+
+        Ok(())
     }
 }
@@ -0,0 +1,198 @@
+//! Serde support for types represented by strings.
+//!
+//! Enums and versions are serialized as their [`Display`](std::fmt::Display) strings and deserialized with
+//! [`FromStr`], e.g. [`Profile::Custom`] is serialized as `"dist:release"` and [`Host`] as
+//! `"x86_64-unknown-linux-gnu"`.
+
+use std::str::FromStr;
+
+use serde::de::{self, Deserialize, Deserializer};
+use serde::ser::{Serialize, Serializer};
+
+use crate::build::DatetimeSource;
+use crate::cargo::{BaseProfile, Profile};
+use crate::package::PackageVersion;
+use crate::rust::{Abi, Architecture, Channel, ChannelVersion, Host, LinuxAbi, System, Toolchain, Vendor, WindowsAbi};
+use crate::target::{Endian, Panic};
+
+/// Implements [`Serialize`] and [`Deserialize`] with [`Display`](std::fmt::Display) and [`FromStr`] traits.
+macro_rules! string {
+    ($($name:ident),+ $(,)?) => {
+        $(
+            impl Serialize for $name {
+                fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+                where
+                    S: Serializer,
+                {
+                    serializer.collect_str(self)
+                }
+            }
+
+            impl<'de> Deserialize<'de> for $name {
+                fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+                where
+                    D: Deserializer<'de>,
+                {
+                    let string = String::deserialize(deserializer)?;
+                    Self::from_str(&string).map_err(de::Error::custom)
+                }
+            }
+        )+
+    };
+}
+
+string!(
+    Abi,
+    Architecture,
+    BaseProfile,
+    Channel,
+    ChannelVersion,
+    DatetimeSource,
+    Endian,
+    Host,
+    LinuxAbi,
+    PackageVersion,
+    Panic,
+    Profile,
+    System,
+    Toolchain,
+    Vendor,
+    WindowsAbi,
+);
+
+#[cfg(test)]
+mod tests {
+    use std::borrow::Cow;
+    use std::fmt::Debug;
+
+    use anyhow::Result;
+    use serde::de::DeserializeOwned;
+
+    use super::*;
+
+    fn round_trip<T>(value: &T, json: &str) -> Result<()>
+    where
+        T: Debug + DeserializeOwned + PartialEq + Serialize,
+    {
+        assert_eq!(serde_json::to_string(value)?, json);
+        assert_eq!(&serde_json::from_str::<T>(json)?, value);
+        Ok(())
+    }
+
+    #[test]
+    fn test_string_round_trip() -> Result<()> {
+        round_trip(&Profile::Release, r#""release""#)?;
+        round_trip(&Profile::from_str("dist:release")?, r#""dist:release""#)?;
+        round_trip(&BaseProfile::Debug, r#""debug""#)?;
+        round_trip(&Channel::Nightly, r#""nightly""#)?;
+        round_trip(&Channel::Version(ChannelVersion::MajorMinor(1, 74)), r#""1.74""#)?;
+        round_trip(&ChannelVersion::MajorMinorPatch(1, 74, 1), r#""1.74.1""#)?;
+        round_trip(&DatetimeSource::SourceDateEpoch, r#""source-date-epoch""#)?;
+        round_trip(&Endian::Little, r#""little""#)?;
+        round_trip(&Panic::ImmediateAbort, r#""immediate-abort""#)?;
+        round_trip(&Panic::Other(Cow::Borrowed("abort-unwind")), r#""abort-unwind""#)?;
+        round_trip(&PackageVersion::from_str("1.2.3-alpha.1")?, r#""1.2.3-alpha.1""#)?;
+        round_trip(
+            &Host::from_str("x86_64-unknown-linux-gnu")?,
+            r#""x86_64-unknown-linux-gnu""#,
+        )?;
+        round_trip(&Host::from_str("future64-foo-bar")?, r#""future64-foo-bar""#)?;
+        round_trip(&Architecture::from_str("aarch64")?, r#""aarch64""#)?;
+        round_trip(
+            &Toolchain::from_str("nightly-2023-01-02-x86_64-unknown-linux-gnu")?,
+            r#""nightly-2023-01-02-x86_64-unknown-linux-gnu""#,
+        )?;
+        Ok(())
+    }
+
+    #[test]
+    fn test_string_deserialize_invalid() {
+        assert!(serde_json::from_str::<Channel>(r#""1.74.1.0""#).is_err());
+        assert!(serde_json::from_str::<Endian>(r#""middle""#).is_err());
+        assert!(serde_json::from_str::<Profile>("1").is_err());
+    }
+
+    #[cfg(feature = "info")]
+    #[test]
+    fn test_build_info_round_trip() -> Result<()> {
+        use std::borrow::Cow;
+
+        use chrono::DateTime;
+
+        use crate::info::{Build, BuildInfo, Cargo, Git, Package, Rust, Target, TargetCfg};
+
+        let host = Host::from_str("x86_64-unknown-linux-gnu")?;
+        let build_info = BuildInfo::new(
+            Some(Build::new(
+                DateTime::parse_from_rfc3339("2023-01-02T10:20:30+01:00")?,
+                DatetimeSource::Fixed,
+            )),
+            Some(Cargo::new(
+                Profile::from_str("dist:release")?,
+                Cow::Borrowed("3"),
+                false,
+                false,
+                Cow::Borrowed(&[Cow::Borrowed("default")]),
+            )),
+            Some(Rust::new(
+                Channel::Stable,
+                Some(ChannelVersion::MajorMinorPatch(1, 74, 1)),
+                None,
+                None,
+                None,
+            )),
+            Some(Target::new(
+                host.clone(),
+                host,
+                TargetCfg::new(
+                    Cow::Borrowed("linux"),
+                    Cow::Borrowed("x86_64"),
+                    Some(Cow::Borrowed("gnu")),
+                    Cow::Borrowed("unknown"),
+                    Cow::Borrowed(&[Cow::Borrowed("unix")]),
+                    Endian::Little,
+                    64,
+                    Cow::Borrowed(&[]),
+                    Cow::Borrowed(&[]),
+                    Some(Panic::Unwind),
+                ),
+            )),
+            Package::new(
+                Cow::Borrowed("crate"),
+                PackageVersion::new(0, 1, 0, None, None),
+                Cow::Borrowed(&[]),
+                None,
+                None,
+                None,
+                Some(Cow::Borrowed("MIT")),
+                None,
+            ),
+            Some(Git::new(
+                Cow::Borrowed("0123456789abcdef0123456789abcdef01234567"),
+                Cow::Borrowed("0123456"),
+                None,
+                true,
+                Some(Cow::Borrowed("main")),
+                None,
+                None,
+            )),
+        );
+
+        let json = serde_json::to_value(&build_info)?;
+        assert_eq!(json["build"]["datetime"], "2023-01-02T10:20:30+01:00");
+        assert_eq!(json["build"]["datetime_source"], "fixed");
+        assert_eq!(json["cargo"]["profile"], "dist:release");
+        assert_eq!(json["cargo"]["features"], serde_json::json!(["default"]));
+        assert_eq!(json["rust"]["channel"], "stable");
+        assert_eq!(json["rust"]["version"], "1.74.1");
+        assert_eq!(json["target"]["host"], "x86_64-unknown-linux-gnu");
+        assert_eq!(json["target"]["cfg"]["endian"], "little");
+        assert_eq!(json["package"]["version"], "0.1.0");
+        assert_eq!(json["git"]["is_dirty"], true);
+        assert!(json["git"].get("dirty").is_none());
+
+        let string = serde_json::to_string(&build_info)?;
+        assert_eq!(serde_json::from_str::<BuildInfo>(&string)?, build_info);
+        Ok(())
+    }
+}
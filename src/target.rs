@@ -0,0 +1,197 @@
+//! Target related types.
+
+use std::borrow::Cow;
+use std::fmt::{self, Display, Formatter};
+use std::result;
+use std::str::FromStr;
+
+use nom::branch::alt;
+use nom::bytes::complete::{tag, take_while1};
+use nom::combinator::all_consuming;
+use nom::error::{context, VerboseError};
+use nom::{Finish, IResult};
+
+use crate::error::Error;
+
+/// An endianness of target.
+///
+/// Resources:
+/// * [The Rust Reference: `target_endian`](https://doc.rust-lang.org/reference/conditional-compilation.html#target_endian).
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Endian {
+    /// Most significant byte first.
+    Big,
+    /// Least significant byte first.
+    Little,
+}
+
+impl Endian {
+    const BIG_STR: &'static str = "big";
+    const LITTLE_STR: &'static str = "little";
+
+    /// Parse endianness.
+    fn nom_parse(input: &str) -> IResult<&str, Self, VerboseError<&str>> {
+        let big = tag(Self::BIG_STR);
+        let little = tag(Self::LITTLE_STR);
+
+        let parser = alt((big, little));
+
+        let (input, endian) = context("endian", parser)(input)?;
+
+        let endian = match endian {
+            Self::BIG_STR => Self::Big,
+            Self::LITTLE_STR => Self::Little,
+            _ => unreachable!(),
+        };
+
+        Ok((input, endian))
+    }
+}
+
+impl Display for Endian {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Big => write!(f, "{}", Self::BIG_STR),
+            Self::Little => write!(f, "{}", Self::LITTLE_STR),
+        }
+    }
+}
+
+impl FromStr for Endian {
+    type Err = Error;
+
+    fn from_str(s: &str) -> result::Result<Self, Self::Err> {
+        let parser = all_consuming(Self::nom_parse);
+
+        let (_, endian) = context("endian", parser)(s).finish().map_err(|error| {
+            let errors = error
+                .errors
+                .into_iter()
+                .map(|(input, kind)| (input.to_string(), kind))
+                .collect();
+            let error = VerboseError { errors };
+            Error::Nom(error)
+        })?;
+
+        Ok(endian)
+    }
+}
+
+/// A panic strategy.
+///
+/// Unrecognised strategies are preserved by [`Panic::Other`] variant.
+///
+/// Resources:
+/// * [The Rust Reference: `panic`](https://doc.rust-lang.org/reference/conditional-compilation.html#panic).
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum Panic {
+    /// Process is aborted.
+    Abort,
+    /// Process is aborted without formatting panic message.
+    ImmediateAbort,
+    /// Stack is unwound.
+    Unwind,
+    /// Unrecognised strategy, preserved as written.
+    Other(Cow<'static, str>),
+}
+
+impl Panic {
+    const ABORT_STR: &'static str = "abort";
+    const IMMEDIATE_ABORT_STR: &'static str = "immediate-abort";
+    const UNWIND_STR: &'static str = "unwind";
+
+    /// Parse panic strategy.
+    fn nom_parse(input: &str) -> IResult<&str, Self, VerboseError<&str>> {
+        let parser = take_while1(|char: char| char.is_ascii_alphanumeric() || char == '-' || char == '_');
+
+        let (input, panic) = context("panic", parser)(input)?;
+
+        let panic = match panic {
+            Self::ABORT_STR => Self::Abort,
+            Self::IMMEDIATE_ABORT_STR => Self::ImmediateAbort,
+            Self::UNWIND_STR => Self::Unwind,
+            other => Self::Other(Cow::Owned(other.to_string())),
+        };
+
+        Ok((input, panic))
+    }
+}
+
+impl Display for Panic {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Abort => write!(f, "{}", Self::ABORT_STR),
+            Self::ImmediateAbort => write!(f, "{}", Self::IMMEDIATE_ABORT_STR),
+            Self::Unwind => write!(f, "{}", Self::UNWIND_STR),
+            Self::Other(other) => write!(f, "{other}"),
+        }
+    }
+}
+
+impl FromStr for Panic {
+    type Err = Error;
+
+    fn from_str(s: &str) -> result::Result<Self, Self::Err> {
+        let parser = all_consuming(Self::nom_parse);
+
+        let (_, panic) = context("panic", parser)(s).finish().map_err(|error| {
+            let errors = error
+                .errors
+                .into_iter()
+                .map(|(input, kind)| (input.to_string(), kind))
+                .collect();
+            let error = VerboseError { errors };
+            Error::Nom(error)
+        })?;
+
+        Ok(panic)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use anyhow::Result;
+
+    use super::*;
+
+    #[test]
+    fn test_endian_display() {
+        assert_eq!(format!("{}", Endian::Big), "big");
+        assert_eq!(format!("{}", Endian::Little), "little");
+    }
+
+    #[test]
+    fn test_endian_from_str() -> Result<()> {
+        assert_eq!(Endian::from_str("big")?, Endian::Big);
+        assert_eq!(Endian::from_str("little")?, Endian::Little);
+        assert!(Endian::from_str("Little").is_err());
+        assert!(Endian::from_str("middle").is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_panic_display() {
+        assert_eq!(format!("{}", Panic::Abort), "abort");
+        assert_eq!(format!("{}", Panic::ImmediateAbort), "immediate-abort");
+        assert_eq!(format!("{}", Panic::Unwind), "unwind");
+        assert_eq!(
+            format!("{}", Panic::Other(Cow::Borrowed("abort-unwind"))),
+            "abort-unwind"
+        );
+    }
+
+    #[test]
+    fn test_panic_from_str() -> Result<()> {
+        assert_eq!(Panic::from_str("abort")?, Panic::Abort);
+        assert_eq!(Panic::from_str("immediate-abort")?, Panic::ImmediateAbort);
+        assert_eq!(Panic::from_str("unwind")?, Panic::Unwind);
+        assert_eq!(
+            Panic::from_str("abort-unwind")?,
+            Panic::Other(Cow::Borrowed("abort-unwind"))
+        );
+        assert_eq!(Panic::from_str("Unwind")?, Panic::Other(Cow::Borrowed("Unwind")));
+        assert!(Panic::from_str("").is_err());
+        assert!(Panic::from_str("abort unwind").is_err());
+        Ok(())
+    }
+}
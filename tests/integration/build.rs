@@ -0,0 +1,14 @@
+use chksum_build::{BuildScript, Result};
+
+fn main() -> Result<()> {
+    BuildScript::default().setup()?;
+    BuildScript::default().prefix("MYAPP").git(false).setup()?;
+    BuildScript::new()
+        .prefix("MINIMAL")
+        .build(false)
+        .cargo(false)
+        .rust(false)
+        .target(false)
+        .git(false)
+        .setup()
+}
@@ -0,0 +1,3 @@
+//! Integration tests of `chksum-build` crate.
+//!
+//! Build script of this crate sets up default, `MYAPP` and `MINIMAL` prefixes, the latter with all sections except package disabled, tests check values read by macros.
@@ -0,0 +1,119 @@
+use anyhow::Result;
+use chksum_build::{build_info, include_build_info, BaseProfile, BuildInfo, Channel, Profile};
+
+include_build_info!();
+
+mod minimal {
+    chksum_build::include_build_info!(prefix = "MINIMAL");
+
+    pub static MINIMAL_BUILD_INFO: chksum_build::BuildInfo = BUILD_INFO;
+}
+
+mod myapp {
+    chksum_build::include_build_info!(prefix = "MYAPP");
+
+    pub static MYAPP_BUILD_INFO: chksum_build::BuildInfo = BUILD_INFO;
+
+    pub fn shared_build_info() -> &'static chksum_build::BuildInfo {
+        build_info()
+    }
+}
+
+const MACROS_BUILD_INFO: BuildInfo = chksum_build_macros::build_info!();
+const MACROS_MINIMAL_BUILD_INFO: BuildInfo = chksum_build_macros::build_info!(prefix = "MINIMAL");
+
+#[test]
+fn test_build_info() -> Result<()> {
+    let build_info = build_info!();
+
+    let cargo = build_info.cargo().unwrap();
+    let base_profile = if cfg!(debug_assertions) {
+        BaseProfile::Debug
+    } else {
+        BaseProfile::Release
+    };
+    assert_eq!(cargo.profile().inherits(), base_profile);
+    assert_eq!(cargo.debug_assertions(), cfg!(debug_assertions));
+    assert!(cargo.has_feature("default"));
+    assert!(cargo.has_feature("std"));
+    assert_eq!(cargo.has_feature("extra-checks"), cfg!(feature = "extra-checks"));
+    assert_eq!(cargo.has_feature("async_io"), cfg!(feature = "async_io"));
+    assert!(!cargo.has_feature("async-io"));
+
+    let target = build_info.target().unwrap();
+    assert_eq!(target.cfg().os(), env!("CHKSUM_BUILD_INFO_TARGET_CFG_OS"));
+    assert_eq!(target.cfg().pointer_width() as usize, usize::BITS as usize);
+    assert_eq!(target.is_cross_compiled(), target.host() != target.target());
+
+    let package = build_info.package();
+    assert_eq!(package.name(), "chksum-build-integration");
+    assert_eq!(package.version().to_string(), "0.0.0");
+
+    assert!(matches!(
+        build_info.rust().unwrap().channel(),
+        Channel::Stable | Channel::Beta | Channel::Nightly | Channel::Version(_)
+    ));
+    Ok(())
+}
+
+#[test]
+fn test_build_info_prefix() -> Result<()> {
+    let build_info = build_info!(prefix = "MYAPP");
+
+    assert_eq!(build_info.cargo(), build_info!().cargo());
+    assert_eq!(build_info.target(), build_info!().target());
+    assert!(build_info.git().is_none());
+    Ok(())
+}
+
+#[test]
+fn test_build_info_disabled_sections() -> Result<()> {
+    let build_info = build_info!(prefix = "MINIMAL");
+
+    assert!(build_info.build().is_none());
+    assert!(build_info.cargo().is_none());
+    assert!(build_info.rust().is_none());
+    assert!(build_info.target().is_none());
+    assert!(build_info.git().is_none());
+    assert_eq!(build_info.package(), build_info!().package());
+    assert_eq!(minimal::MINIMAL_BUILD_INFO, build_info);
+    assert_eq!(MACROS_MINIMAL_BUILD_INFO, build_info);
+    Ok(())
+}
+
+#[test]
+fn test_include_build_info() -> Result<()> {
+    let build_info = build_info!();
+
+    assert_eq!(BUILD_INFO, build_info);
+    assert_eq!(myapp::MYAPP_BUILD_INFO, build_info!(prefix = "MYAPP"));
+    assert!(!matches!(BUILD_INFO.cargo().unwrap().profile(), Profile::Custom { .. }));
+    Ok(())
+}
+
+#[test]
+fn test_include_build_info_static() {
+    let build_info = build_info();
+
+    assert_eq!(*build_info, BUILD_INFO);
+    assert!(std::ptr::eq(build_info, self::build_info()));
+    assert!(std::ptr::eq(myapp::shared_build_info(), myapp::shared_build_info()));
+    assert!(!std::ptr::eq(build_info, myapp::shared_build_info()));
+}
+
+#[test]
+fn test_macros_build_info() -> Result<()> {
+    assert_eq!(MACROS_BUILD_INFO, build_info!());
+    Ok(())
+}
+
+#[test]
+fn test_build_info_serde() -> Result<()> {
+    let json = serde_json::to_value(build_info())?;
+    assert_eq!(json["package"]["name"], "chksum-build-integration");
+    assert_eq!(json["target"]["cfg"]["os"], env!("CHKSUM_BUILD_INFO_TARGET_CFG_OS"));
+
+    let build_info = serde_json::from_value::<BuildInfo>(json)?;
+    assert_eq!(build_info, BUILD_INFO);
+    Ok(())
+}